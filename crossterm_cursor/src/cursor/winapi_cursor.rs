@@ -2,6 +2,8 @@
 //! This module is used for Windows terminals that do not support ANSI escape codes.
 //! Note that the cursor position is 0 based. This means that we start counting at 0 when setting the cursor position.
 
+use std::convert::TryFrom;
+
 use crossterm_utils::Result;
 
 use crate::sys::winapi::{Cursor, Handle};
@@ -26,7 +28,11 @@ impl ITerminalCursor for WinApiCursor {
 
     fn pos(&self) -> (u16, u16) {
         let cursor = Cursor::new().unwrap();
-        cursor.position().unwrap().into()
+        let position = cursor.position().unwrap();
+
+        // the console never reports a negative position, but clamp it to the first cell instead of panicking if it does.
+        <(u16, u16)>::try_from(position)
+            .unwrap_or_else(|_| (position.x.max(0) as u16, position.y.max(0) as u16))
     }
 
     fn move_up(&self, count: u16) -> Result<()> {
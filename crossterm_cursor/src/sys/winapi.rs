@@ -1,5 +1,6 @@
 //! This module handles some logic for cursor interaction in the windows console.
 
+use std::convert::TryFrom;
 use std::io::{self, Result};
 
 use winapi::{
@@ -12,11 +13,10 @@ pub use crossterm_winapi::{is_true, Coord, Handle, HandleType, ScreenBuffer};
 
 #[cfg(windows)]
 pub fn get_cursor_position() -> (u16, u16) {
-    if let Ok(cursor) = Cursor::new() {
-        cursor.position().unwrap().into()
-    } else {
-        (0, 0)
-    }
+    Cursor::new()
+        .and_then(|cursor| cursor.position())
+        .and_then(<(u16, u16)>::try_from)
+        .unwrap_or((0, 0))
 }
 
 #[cfg(windows)]
@@ -110,10 +110,10 @@ impl Cursor {
     /// Save current cursor position to recall later.
     pub fn save_cursor_pos() -> io::Result<()> {
         let cursor = Cursor::new()?;
-        let position = cursor.position()?;
+        let position = <(u16, u16)>::try_from(cursor.position()?)?;
 
         unsafe {
-            SAVED_CURSOR_POS = (position.x as u16, position.y as u16);
+            SAVED_CURSOR_POS = position;
         }
 
         Ok(())
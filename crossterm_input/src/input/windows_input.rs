@@ -22,8 +22,7 @@ use winapi::um::{
 
 use crossterm_utils::Result;
 use crossterm_winapi::{
-    ButtonState, Console, ConsoleMode, EventFlags, Handle, InputEventType, KeyEventRecord,
-    MouseEvent,
+    ButtonState, Console, ConsoleMode, Event, EventFlags, Handle, KeyEventRecord, MouseEvent,
 };
 
 use super::{ITerminalInput, InputEvent, KeyEvent, MouseButton};
@@ -224,17 +223,13 @@ fn read_single_event() -> Result<Option<InputEvent>> {
         None => return Ok(None),
     };
 
-    match input.event_type {
-        InputEventType::KeyEvent => {
-            handle_key_event(unsafe { KeyEventRecord::from(*input.event.KeyEvent()) })
-        }
-        InputEventType::MouseEvent => {
-            handle_mouse_event(unsafe { MouseEvent::from(*input.event.MouseEvent()) })
-        }
+    match input.event() {
+        Event::Key(key_event) => handle_key_event(key_event),
+        Event::Mouse(mouse_event) => handle_mouse_event(mouse_event),
         // NOTE (@imdaveho): ignore below
-        InputEventType::WindowBufferSizeEvent => return Ok(None), // TODO implement terminal resize event
-        InputEventType::FocusEvent => Ok(None),
-        InputEventType::MenuEvent => Ok(None),
+        Event::WindowBufferSize(_) => return Ok(None), // TODO implement terminal resize event
+        Event::Focus(_) => Ok(None),
+        Event::Menu(_) => Ok(None),
     }
 }
 
@@ -247,25 +242,21 @@ fn read_input_events() -> Result<(u32, Vec<InputEvent>)> {
     let mut input_events = Vec::with_capacity(result.0 as usize);
 
     for input in result.1 {
-        match input.event_type {
-            InputEventType::KeyEvent => {
-                if let Ok(Some(event)) =
-                    handle_key_event(unsafe { KeyEventRecord::from(*input.event.KeyEvent()) })
-                {
+        match input.event() {
+            Event::Key(key_event) => {
+                if let Ok(Some(event)) = handle_key_event(key_event) {
                     input_events.push(event)
                 }
             }
-            InputEventType::MouseEvent => {
-                if let Ok(Some(event)) =
-                    handle_mouse_event(unsafe { MouseEvent::from(*input.event.MouseEvent()) })
-                {
+            Event::Mouse(mouse_event) => {
+                if let Ok(Some(event)) = handle_mouse_event(mouse_event) {
                     input_events.push(event)
                 }
             }
             // NOTE (@imdaveho): ignore below
-            InputEventType::WindowBufferSizeEvent => (), // TODO implement terminal resize event
-            InputEventType::FocusEvent => (),
-            InputEventType::MenuEvent => (),
+            Event::WindowBufferSize(_) => (), // TODO implement terminal resize event
+            Event::Focus(_) => (),
+            Event::Menu(_) => (),
         }
     }
 
@@ -368,7 +359,7 @@ fn parse_key_event_record(key_event: &KeyEventRecord) -> Option<KeyEvent> {
         VK_INSERT => Some(KeyEvent::Insert),
         _ => {
             // Modifier Keys (Ctrl, Alt, Shift) Support
-            let character_raw = { (unsafe { *key_event.u_char.UnicodeChar() } as u16) };
+            let character_raw = key_event.u_char as u32;
 
             if character_raw < 255 {
                 let character = character_raw as u8 as char;
@@ -7,7 +7,7 @@ use std::sync::Once;
 use winapi::um::wincon;
 
 use crossterm_utils::Result;
-use crossterm_winapi::{Console, Handle, HandleType, ScreenBuffer};
+use crossterm_winapi::{Console, ConsoleColor, Handle, HandleType, ScreenBuffer};
 
 use crate::{Color, Colored, ITerminalColor};
 
@@ -97,82 +97,61 @@ impl ITerminalColor for WinApiColor {
 
 /// This will get the winapi color value from the Color and ColorType struct
 fn color_value(color: Colored) -> String {
-    let winapi_color: u16;
-
-    match color {
-        Colored::Fg(color) => {
-            winapi_color = match color {
-                Color::Black => 0,
-                Color::DarkGrey => FG_INTENSITY,
-                Color::Red => FG_INTENSITY | FG_RED,
-                Color::DarkRed => FG_RED,
-                Color::Green => FG_INTENSITY | FG_GREEN,
-                Color::DarkGreen => FG_GREEN,
-                Color::Yellow => FG_INTENSITY | FG_GREEN | FG_RED,
-                Color::DarkYellow => FG_GREEN | FG_RED,
-                Color::Blue => FG_INTENSITY | FG_BLUE,
-                Color::DarkBlue => FG_BLUE,
-                Color::Magenta => FG_INTENSITY | FG_RED | FG_BLUE,
-                Color::DarkMagenta => FG_RED | FG_BLUE,
-                Color::Cyan => FG_INTENSITY | FG_GREEN | FG_BLUE,
-                Color::DarkCyan => FG_GREEN | FG_BLUE,
-                Color::White => FG_RED | FG_GREEN | FG_BLUE,
-                Color::Grey => FG_INTENSITY | FG_RED | FG_GREEN | FG_BLUE,
-
-                Color::Reset => {
-                    // init the original color in case it is not set.
-                    let mut original_color = original_console_color();
-
-                    const REMOVE_BG_MASK: u16 = BG_INTENSITY | BG_RED | BG_GREEN | BG_BLUE;
-                    // remove all background values from the original color, we don't want to reset those.
-                    original_color &= !(REMOVE_BG_MASK);
-
-                    original_color
-                }
-
-                /* WinApi will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiBValue) colors are not supported in that case.*/
-                Color::Rgb { r: _, g: _, b: _ } => 0,
-                Color::AnsiValue(_val) => 0,
-            };
+    let winapi_color: u16 = match color {
+        Colored::Fg(Color::Reset) => {
+            // init the original color in case it is not set.
+            let mut original_color = original_console_color();
+
+            const REMOVE_BG_MASK: u16 = BG_INTENSITY | BG_RED | BG_GREEN | BG_BLUE;
+            // remove all background values from the original color, we don't want to reset those.
+            original_color &= !(REMOVE_BG_MASK);
+
+            original_color
         }
-        Colored::Bg(color) => {
-            winapi_color = match color {
-                Color::Black => 0,
-                Color::DarkGrey => BG_INTENSITY,
-                Color::Red => BG_INTENSITY | BG_RED,
-                Color::DarkRed => BG_RED,
-                Color::Green => BG_INTENSITY | BG_GREEN,
-                Color::DarkGreen => BG_GREEN,
-                Color::Yellow => BG_INTENSITY | BG_GREEN | BG_RED,
-                Color::DarkYellow => BG_GREEN | BG_RED,
-                Color::Blue => BG_INTENSITY | BG_BLUE,
-                Color::DarkBlue => BG_BLUE,
-                Color::Magenta => BG_INTENSITY | BG_RED | BG_BLUE,
-                Color::DarkMagenta => BG_RED | BG_BLUE,
-                Color::Cyan => BG_INTENSITY | BG_GREEN | BG_BLUE,
-                Color::DarkCyan => BG_GREEN | BG_BLUE,
-                Color::White => BG_INTENSITY | BG_RED | BG_GREEN | BG_BLUE,
-                Color::Grey => BG_RED | BG_GREEN | BG_BLUE,
-
-                Color::Reset => {
-                    // init the original color in case it is not set.
-                    let mut original_color = original_console_color();
-
-                    const REMOVE_FG_MASK: u16 = FG_INTENSITY | FG_RED | FG_GREEN | FG_BLUE;
-                    // remove all foreground values from the original color, we don't want to reset those.
-                    original_color &= !(REMOVE_FG_MASK);
-                    original_color
-                }
-                /* WinApi will be used for systems that do not support ANSI, those are windows version less then 10. RGB and 255 (AnsiBValue) colors are not supported in that case.*/
-                Color::Rgb { r: _, g: _, b: _ } => 0,
-                Color::AnsiValue(_val) => 0,
-            };
+        Colored::Bg(Color::Reset) => {
+            // init the original color in case it is not set.
+            let mut original_color = original_console_color();
+
+            const REMOVE_FG_MASK: u16 = FG_INTENSITY | FG_RED | FG_GREEN | FG_BLUE;
+            // remove all foreground values from the original color, we don't want to reset those.
+            original_color &= !(REMOVE_FG_MASK);
+            original_color
         }
+        Colored::Fg(color) => console_color(color).map_or(0, ConsoleColor::foreground_bits),
+        Colored::Bg(color) => console_color(color).map_or(0, ConsoleColor::background_bits),
     };
 
     winapi_color.to_string()
 }
 
+/// Maps a `Color` to the nearest of the 16 console colors.
+///
+/// RGB and 256 (AnsiValue) colors are not supported by the consoles that use WinApi, those are windows versions less then 10,
+/// so they are quantized to the nearest console color. `Color::Reset` has no console color and returns `None`.
+fn console_color(color: Color) -> Option<ConsoleColor> {
+    Some(match color {
+        Color::Reset => return None,
+        Color::Black => ConsoleColor::Black,
+        Color::DarkGrey => ConsoleColor::DarkGrey,
+        Color::Red => ConsoleColor::Red,
+        Color::DarkRed => ConsoleColor::DarkRed,
+        Color::Green => ConsoleColor::Green,
+        Color::DarkGreen => ConsoleColor::DarkGreen,
+        Color::Yellow => ConsoleColor::Yellow,
+        Color::DarkYellow => ConsoleColor::DarkYellow,
+        Color::Blue => ConsoleColor::Blue,
+        Color::DarkBlue => ConsoleColor::DarkBlue,
+        Color::Magenta => ConsoleColor::Magenta,
+        Color::DarkMagenta => ConsoleColor::DarkMagenta,
+        Color::Cyan => ConsoleColor::Cyan,
+        Color::DarkCyan => ConsoleColor::DarkCyan,
+        Color::White => ConsoleColor::White,
+        Color::Grey => ConsoleColor::Grey,
+        Color::Rgb { r, g, b } => ConsoleColor::from_rgb(r, g, b),
+        Color::AnsiValue(value) => ConsoleColor::from_ansi_value(value),
+    })
+}
+
 fn init_console_color() -> io::Result<()> {
     let screen_buffer = ScreenBuffer::current()?;
 
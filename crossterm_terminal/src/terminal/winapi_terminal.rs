@@ -182,8 +182,14 @@ pub fn clear_after_cursor(
     // location where to start clearing
     let start_location = Coord::new(x, y);
 
-    // get sum cells before cursor
-    let cells_to_write = buffer_size.width as u32 * buffer_size.height as u32;
+    // get sum cells from the cursor until the end of the buffer
+    let cells_to_write = (buffer_size.width as u32 * buffer_size.height as u32)
+        .saturating_sub(buffer_size.width as u32 * y as u32 + x as u32);
+
+    // the cursor was in the last cell, so there is nothing after it to clear
+    if cells_to_write == 0 || y >= buffer_size.height {
+        return Ok(());
+    }
 
     clear(start_location, cells_to_write, current_attribute)
 }
@@ -215,7 +221,7 @@ pub fn clear_entire_screen(buffer_size: Size, current_attribute: u16) -> Result<
     let cells_to_write = buffer_size.width as u32 * buffer_size.height as u32;
 
     // location where to start clearing
-    let start_location = Coord::new(0, 0);
+    let start_location = Coord::ORIGIN;
 
     // clear the entire screen
     clear(start_location, cells_to_write, current_attribute)?;
@@ -266,15 +272,23 @@ pub fn clear_until_line(location: Coord, buffer_size: Size, current_attribute: u
 
 fn clear(start_location: Coord, cells_to_write: u32, current_attribute: u16) -> Result<()> {
     let console = Console::from(Handle::current_out_handle()?);
-    let _ = console.fill_whit_character(start_location, cells_to_write, ' ')?;
-    console.fill_whit_attribute(start_location, cells_to_write, current_attribute)?;
+    let _ = console.fill_with_character(start_location, cells_to_write, ' ')?;
+    console.fill_with_attribute(start_location, cells_to_write, current_attribute)?;
 
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{ITerminal, WinApiTerminal};
+    use crossterm_winapi::{Coord, Size};
+
+    use super::{clear_after_cursor, ITerminal, WinApiTerminal};
+
+    #[test]
+    fn clear_after_last_cell() {
+        // a cursor past the right edge of the last row has no cells after it.
+        assert!(clear_after_cursor(Coord::new(81, 24), Size::new(80, 25), 0x07).is_ok());
+    }
 
     // TODO - Test is ignored, because it returns wrong result (31 != 30)
     #[test]
@@ -1,5 +1,7 @@
+#[cfg(windows)]
 use crossterm_winapi::{Console, ScreenBuffer};
 
+#[cfg(windows)]
 fn set_background_color() -> std::io::Result<()> {
     // background value
     const BLUE_BACKGROUND: u16 = 0x0010;
@@ -21,6 +23,7 @@ fn set_background_color() -> std::io::Result<()> {
     Ok(())
 }
 
+#[cfg(windows)]
 fn set_foreground_color() -> std::io::Result<()> {
     // background value
     const BLUE_FOREGROUND: u16 = 0x0001;
@@ -46,7 +49,11 @@ fn set_foreground_color() -> std::io::Result<()> {
     Ok(())
 }
 
+#[cfg(windows)]
 fn main() {
     set_background_color().unwrap();
     set_foreground_color().unwrap();
 }
+
+#[cfg(not(windows))]
+fn main() {}
@@ -1,17 +1,23 @@
+#[cfg(windows)]
 use crossterm_winapi::ConsoleMode;
 
+#[cfg(windows)]
 fn change_console_mode() {
     let console_mode = ConsoleMode::new().unwrap();
 
     // get the current console mode:
     let _mode: u32 = console_mode.mode().unwrap();
 
-    // set the console mode (not sure if this is an actual value xp)
+    // set the console mode, the flags are combined with a bitwise or.
     console_mode
-        .set_mode(10)
+        .set_mode(ConsoleMode::ENABLE_PROCESSED_OUTPUT | ConsoleMode::ENABLE_WRAP_AT_EOL_OUTPUT)
         .expect("Unable to set console mode");
 }
 
+#[cfg(windows)]
 fn main() {
     change_console_mode();
 }
+
+#[cfg(not(windows))]
+fn main() {}
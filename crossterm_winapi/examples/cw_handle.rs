@@ -1,6 +1,8 @@
+#[cfg(windows)]
 use crossterm_winapi::{Handle, HandleType};
 
 #[allow(unused_variables)]
+#[cfg(windows)]
 fn main() {
     // see the description of the types to see what they do.
     let out_put_handle = Handle::new(HandleType::OutputHandle).unwrap();
@@ -16,3 +18,6 @@ fn main() {
     // you can also pass you own `HANDLE` to create an instance of `Handle`
     let handle = Handle::from(handle); /* winapi::um::winnt::HANDLE */
 }
+
+#[cfg(not(windows))]
+fn main() {}
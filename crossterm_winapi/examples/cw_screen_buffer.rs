@@ -1,5 +1,7 @@
+#[cfg(windows)]
 use crossterm_winapi::ScreenBuffer;
 
+#[cfg(windows)]
 fn print_screen_buffer_information() {
     let screen_buffer = ScreenBuffer::current().unwrap();
 
@@ -13,6 +15,7 @@ fn print_screen_buffer_information() {
 }
 
 #[allow(dead_code)]
+#[cfg(windows)]
 fn multiple_screen_buffers() {
     // create new screen buffer
     let screen_buffer = ScreenBuffer::create();
@@ -21,6 +24,10 @@ fn multiple_screen_buffers() {
     screen_buffer.show().expect("Unable to show screen buffer");
 }
 
+#[cfg(windows)]
 fn main() {
     print_screen_buffer_information();
 }
+
+#[cfg(not(windows))]
+fn main() {}
@@ -0,0 +1,283 @@
+//! This module provides a way to record console operations and apply them at once, like `queue!` does for ANSI commands.
+
+use std::io::Result;
+
+use super::{char_cell_width, CharInfo, Console, Coord, WindowPositions};
+
+/// A single recorded operation of a `ConsoleBatch`.
+enum Operation {
+    SetAttribute(u16),
+    Goto(Coord),
+    Write(String),
+    Fill {
+        start: Coord,
+        count: u32,
+        ch: char,
+        attributes: u16,
+    },
+}
+
+/// Records a sequence of console operations and applies them in one `execute` call.
+///
+/// Setting the attributes and moving the cursor are only tracked while executing.
+/// Consecutive writes of plain text that fit on the current line are collected into a single run of cells,
+/// which is written with one `WriteConsoleOutputW` call. The cursor position and attributes are only set on the
+/// console when needed, so after `execute` the console ends up in the same state as if every operation was applied one by one.
+///
+/// ```no_run
+/// # use crossterm_winapi::{Console, ConsoleBatch, Coord};
+/// # fn main() -> std::io::Result<()> {
+/// let console = Console::new()?;
+///
+/// ConsoleBatch::new()
+///     .goto(Coord::new(2, 1))
+///     .set_attribute(0x0E)
+///     .write("score: ")
+///     .write("42")
+///     .execute(&console)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Default)]
+pub struct ConsoleBatch {
+    operations: Vec<Operation>,
+}
+
+impl ConsoleBatch {
+    /// Create a new empty batch.
+    pub fn new() -> ConsoleBatch {
+        ConsoleBatch::default()
+    }
+
+    /// Record setting the attributes of the characters written after this operation, like `Console::set_text_attribute`.
+    pub fn set_attribute(&mut self, attributes: u16) -> &mut ConsoleBatch {
+        self.operations.push(Operation::SetAttribute(attributes));
+        self
+    }
+
+    /// Record moving the cursor, like `Console::set_cursor_position`.
+    pub fn goto(&mut self, position: Coord) -> &mut ConsoleBatch {
+        self.operations.push(Operation::Goto(position));
+        self
+    }
+
+    /// Record writing text at the cursor, like `Console::write_str`.
+    pub fn write(&mut self, text: &str) -> &mut ConsoleBatch {
+        self.operations.push(Operation::Write(text.to_string()));
+        self
+    }
+
+    /// Record filling `count` cells starting at `start` with a character and attributes,
+    /// like `Console::fill_with_character_wide` followed by `Console::fill_with_attribute`.
+    pub fn fill(
+        &mut self,
+        start: Coord,
+        count: u32,
+        ch: char,
+        attributes: u16,
+    ) -> &mut ConsoleBatch {
+        self.operations.push(Operation::Fill {
+            start,
+            count,
+            ch,
+            attributes,
+        });
+        self
+    }
+
+    /// Returns whether no operations were recorded.
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Apply all recorded operations to the given console, in the order they were recorded.
+    ///
+    /// The recorded operations are kept, so the same batch could be executed again.
+    pub fn execute(&self, console: &Console) -> Result<()> {
+        let info = console.screen_buffer_info()?;
+        let width = info.buffer_size().width;
+
+        let mut state = ExecuteState {
+            console,
+            cursor: info.cursor_pos(),
+            attributes: info.attributes(),
+            console_cursor: info.cursor_pos(),
+            console_attributes: info.attributes(),
+            run_start: info.cursor_pos(),
+            run: Vec::new(),
+        };
+
+        for operation in &self.operations {
+            match operation {
+                Operation::SetAttribute(attributes) => state.attributes = *attributes,
+                Operation::Goto(position) => state.cursor = *position,
+                Operation::Write(text) => {
+                    let len = text.chars().count();
+
+                    if text.chars().all(is_plain)
+                        && i32::from(state.cursor.x) + (len as i32) < i32::from(width)
+                    {
+                        state.push_run(text, len)?;
+                    } else {
+                        state.write_direct(text)?;
+                    }
+                }
+                Operation::Fill {
+                    start,
+                    count,
+                    ch,
+                    attributes,
+                } => {
+                    state.flush_run()?;
+                    console.fill_with_character_wide(*start, *count, *ch)?;
+                    console.fill_with_attribute(*start, *count, *attributes)?;
+                }
+            }
+        }
+
+        state.flush_run()?;
+        state.sync()
+    }
+}
+
+/// The state that is tracked while executing a batch.
+struct ExecuteState<'a> {
+    console: &'a Console,
+    // the cursor position and attributes as they would be after the operations so far.
+    cursor: Coord,
+    attributes: u16,
+    // the cursor position and attributes the console actually has.
+    console_cursor: Coord,
+    console_attributes: u16,
+    // cells that still have to be written, starting at `run_start`.
+    run_start: Coord,
+    run: Vec<CharInfo>,
+}
+
+impl ExecuteState<'_> {
+    /// Add plain text at the cursor to the current run of cells, starting a new run if it's not contiguous.
+    fn push_run(&mut self, text: &str, len: usize) -> Result<()> {
+        let run_end = Coord::new(self.run_start.x + self.run.len() as i16, self.run_start.y);
+
+        if self.run.is_empty() || run_end != self.cursor {
+            self.flush_run()?;
+            self.run_start = self.cursor;
+        }
+
+        let attributes = self.attributes;
+        self.run
+            .extend(text.chars().map(|ch| CharInfo::new(ch, attributes)));
+        self.cursor.x += len as i16;
+
+        Ok(())
+    }
+
+    /// Write text that can't be part of a run, like text that wraps or contains control characters, with `WriteConsoleW`.
+    fn write_direct(&mut self, text: &str) -> Result<()> {
+        self.flush_run()?;
+        self.sync()?;
+        self.console.write_str(text)?;
+
+        self.cursor = self.console.screen_buffer_info()?.cursor_pos();
+        self.console_cursor = self.cursor;
+        Ok(())
+    }
+
+    /// Write the current run of cells in one call.
+    fn flush_run(&mut self) -> Result<()> {
+        if self.run.is_empty() {
+            return Ok(());
+        }
+
+        let len = self.run.len() as i16;
+        let region = WindowPositions {
+            left: self.run_start.x,
+            right: self.run_start.x + len - 1,
+            bottom: self.run_start.y,
+            top: self.run_start.y,
+        };
+
+        self.console
+            .write_output(&self.run, Coord::new(len, 1), Coord::ORIGIN, region)?;
+        self.run.clear();
+        Ok(())
+    }
+
+    /// Set the tracked cursor position and attributes on the console, if they differ.
+    fn sync(&mut self) -> Result<()> {
+        if self.cursor != self.console_cursor {
+            self.console.set_cursor_position(self.cursor)?;
+            self.console_cursor = self.cursor;
+        }
+
+        if self.attributes != self.console_attributes {
+            self.console.set_text_attribute(self.attributes)?;
+            self.console_attributes = self.attributes;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether the character takes a single cell and doesn't move the cursor in any special way,
+/// so it can be written as a cell with `WriteConsoleOutputW`.
+///
+/// Wide characters and combining marks, see `char_cell_width`, and characters that need a surrogate pair are not plain,
+/// because they don't fill exactly one `CHAR_INFO` cell.
+fn is_plain(ch: char) -> bool {
+    !ch.is_control() && ch.len_utf16() == 1 && char_cell_width(ch) == 1
+}
+
+#[cfg(test)]
+mod test {
+    use super::{is_plain, ConsoleBatch};
+    use crate::testutil::with_scratch_console;
+    use crate::{Coord, WindowPositions};
+
+    #[test]
+    fn plain_characters() {
+        assert!(is_plain('a'));
+        assert!(is_plain('é'));
+        assert!(!is_plain('\n'));
+        assert!(!is_plain('\t'));
+        assert!(!is_plain('漢'));
+        assert!(!is_plain('😀'));
+        assert!(!is_plain('\u{0301}'));
+        assert!(!is_plain('\u{1F321}'));
+    }
+
+    #[test]
+    fn execute_batch() {
+        with_scratch_console(|console| {
+            ConsoleBatch::new()
+                .goto(Coord::new(1, 0))
+                .set_attribute(0x1F)
+                .write("ab")
+                .write("cd")
+                .fill(Coord::new(0, 1), 3, '#', 0x0E)
+                .execute(console)
+                .unwrap();
+
+            let (cells, _) = console
+                .read_output(
+                    Coord::new(5, 2),
+                    Coord::ORIGIN,
+                    WindowPositions {
+                        left: 0,
+                        right: 4,
+                        bottom: 1,
+                        top: 0,
+                    },
+                )
+                .unwrap();
+
+            let text: String = cells.iter().map(|cell| cell.ch).collect();
+            assert_eq!(text, " abcd###  ");
+            assert_eq!(cells[1].attributes, 0x1F);
+            assert_eq!(cells[5].attributes, 0x0E);
+
+            assert_eq!(console.cursor_position().unwrap(), Coord::new(5, 0));
+            assert_eq!(console.current_attributes().unwrap(), 0x1F);
+        });
+    }
+}
@@ -1,24 +1,127 @@
-use std::borrow::ToOwned;
+use std::cell::Cell;
+use std::collections::VecDeque;
+use std::convert::TryFrom;
 use std::io::{self, Error, Result};
+use std::mem::{size_of, zeroed};
+use std::ptr;
 use std::str;
+use std::time::Duration;
 
 use winapi::ctypes::c_void;
-use winapi::shared::minwindef::DWORD;
+use winapi::shared::minwindef::{DWORD, FALSE, TRUE};
 use winapi::shared::ntdef::NULL;
-use winapi::um::consoleapi::{GetNumberOfConsoleInputEvents, ReadConsoleInputW, WriteConsoleW};
+use winapi::shared::windef::{HMONITOR, HWND, RECT};
+use winapi::shared::winerror::{ERROR_INVALID_HANDLE, WAIT_TIMEOUT};
+use winapi::um::consoleapi::{
+    GetConsoleCP, GetConsoleOutputCP, GetNumberOfConsoleInputEvents, ReadConsoleInputW,
+    ReadConsoleW, WriteConsoleW,
+};
 use winapi::um::{
+    errhandlingapi::SetLastError,
+    handleapi::INVALID_HANDLE_VALUE,
+    synchapi::WaitForSingleObject,
+    utilapiset::Beep,
+    winbase::{INFINITE, WAIT_FAILED, WAIT_OBJECT_0},
     wincon::{
-        FillConsoleOutputAttribute, FillConsoleOutputCharacterA, GetLargestConsoleWindowSize,
-        SetConsoleTextAttribute, SetConsoleWindowInfo, COORD, INPUT_RECORD, SMALL_RECT,
+        CreateConsoleScreenBuffer, FillConsoleOutputAttribute, FillConsoleOutputCharacterA,
+        FillConsoleOutputCharacterW, FlushConsoleInputBuffer, GenerateConsoleCtrlEvent,
+        GetConsoleCursorInfo, GetConsoleScreenBufferInfo, GetConsoleScreenBufferInfoEx,
+        GetConsoleSelectionInfo, GetConsoleTitleW, GetConsoleWindow, GetCurrentConsoleFontEx,
+        GetLargestConsoleWindowSize, GetNumberOfConsoleMouseButtons, PeekConsoleInputW,
+        ReadConsoleOutputAttribute, ReadConsoleOutputCharacterW, ReadConsoleOutputW,
+        ScrollConsoleScreenBufferW, SetConsoleActiveScreenBuffer, SetConsoleCP,
+        SetConsoleCursorInfo, SetConsoleCursorPosition, SetConsoleOutputCP,
+        SetConsoleScreenBufferInfoEx, SetConsoleScreenBufferSize, SetConsoleTextAttribute,
+        SetConsoleTitleW, SetConsoleWindowInfo, SetCurrentConsoleFontEx, WriteConsoleInputW,
+        WriteConsoleOutputAttribute, WriteConsoleOutputCharacterW, WriteConsoleOutputW, CHAR_INFO,
+        CONSOLE_CURSOR_INFO, CONSOLE_FONT_INFOEX, CONSOLE_SCREEN_BUFFER_INFOEX,
+        CONSOLE_SELECTION_INFO, CONSOLE_TEXTMODE_BUFFER, COORD, CTRL_BREAK_EVENT, CTRL_C_EVENT,
+        INPUT_RECORD, SMALL_RECT,
+    },
+    winnt::{FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, GENERIC_WRITE, HANDLE},
+    winuser::{
+        AdjustWindowRectEx, GetMonitorInfoW, GetSystemMetrics, GetWindowLongW, MonitorFromWindow,
+        GWL_STYLE, MONITORINFO, MONITOR_DEFAULTTONEAREST, SM_CXVSCROLL, WS_OVERLAPPEDWINDOW,
     },
-    winnt::HANDLE,
 };
 
-use super::{is_true, Coord, Handle, HandleType, InputRecord, WindowPositions};
+#[cfg(feature = "async")]
+use super::ReadableFuture;
+use super::{
+    attributes, char_cell_width, is_true, vt, AttributeGuard, CharInfo, CharSpansMultipleCells,
+    ConsoleColor, ConsoleError, ConsoleMode, Coord, Event, FontInfo, Handle, HandleType,
+    InputRecord, MouseCaptureGuard, RawModeGuard, ResizeEvent, ResizeWatcher, ScreenBufferInfo,
+    SelectionInfo, Size, VtCaps, WindowPositions,
+};
+
+/// The size of the buffer, in UTF-16 code units, used to read the console title.
+const MAX_TITLE_LENGTH: usize = 1024;
+
+/// The maximum number of UTF-16 code units passed to a single `WriteConsoleW` call.
+const MAX_WRITE_LENGTH: usize = 32 * 1024;
+
+/// The maximum number of cells read or written with a single `ReadConsoleOutputW` or `WriteConsoleOutputW` call.
+const MAX_OUTPUT_CELLS: usize = 8 * 1024;
+
+/// The outcome of a write to the console, see `Console::write_char_buffer_checked`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CharsWritten {
+    /// The number of bytes from the input that were written to the console.
+    pub bytes_consumed: usize,
+    /// The number of UTF-16 code units the console reported as written.
+    pub cells_written: u32,
+}
+
+/// A copy of the whole screen buffer, see `Console::save_screen`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScreenSnapshot {
+    size: Coord,
+    cells: Vec<CharInfo>,
+    cursor_position: Coord,
+    attributes: u16,
+}
+
+impl ScreenSnapshot {
+    /// Get the size of the screen buffer when the snapshot was taken, `x` being the width and `y` the height.
+    pub fn size(&self) -> Coord {
+        self.size
+    }
+
+    /// Get the cells of the screen buffer, stored row by row.
+    pub fn cells(&self) -> &[CharInfo] {
+        &self.cells
+    }
+
+    /// Get the cursor position when the snapshot was taken.
+    pub fn cursor_position(&self) -> Coord {
+        self.cursor_position
+    }
+
+    /// Get the attributes when the snapshot was taken.
+    pub fn attributes(&self) -> u16 {
+        self.attributes
+    }
+}
+
+/// The control signal to send with `Console::generate_ctrl_event`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum CtrlEvent {
+    /// This represents the `CTRL_C_EVENT`
+    C,
+    /// This represents the `CTRL_BREAK_EVENT`
+    Break,
+}
 
 /// Could be used to do some basic things with the console.
+///
+/// Most functions only work for either an input or an output handle. When the `Console` is created from a `Handle` of a known `HandleType`,
+/// calling a function of the other direction returns an `InvalidInput` error before anything is called.
 pub struct Console {
     handle: Handle,
+    // the cached result of `supports_virtual_terminal`.
+    supports_vt: Cell<Option<bool>>,
+    // the size of the screen buffer before `maximize` grew it, which `restore` shrinks it back to.
+    unmaximized_buffer: Cell<Option<Coord>>,
 }
 
 impl Console {
@@ -26,9 +129,75 @@ impl Console {
     ///
     /// This created instance will use the default output handle (STD_OUTPUT_HANDLE) as handle for the function call it wraps.
     pub fn new() -> Result<Console> {
-        Ok(Console {
-            handle: Handle::new(HandleType::OutputHandle)?,
-        })
+        Console::with_handle_type(HandleType::OutputHandle)
+    }
+
+    /// Create new instance of `Console` that uses the handle of the given type for the function calls it wraps.
+    ///
+    /// Use an input handle type, like `HandleType::CurrentInputHandle`, to read input with `read_console_input`.
+    pub fn with_handle_type(handle_type: HandleType) -> Result<Console> {
+        Ok(Console::from(Handle::new(handle_type)?))
+    }
+
+    /// Create new instance of `Console` that uses a duplicate of the given handle, so the handle itself remains usable.
+    ///
+    /// The duplicate keeps the `HandleType` of `handle` and is closed when the `Console` is dropped, see `Handle::try_clone`.
+    pub fn from_ref(handle: &Handle) -> Result<Console> {
+        Ok(Console::from(handle.try_clone()?))
+    }
+
+    /// Create a new console screen buffer and return a `Console` bound to it.
+    ///
+    /// The new buffer is not shown until `activate` is called on it, and it is closed when the returned `Console` is dropped.
+    /// To switch back, keep the `Console` of the original buffer alive and call `activate` on that one:
+    ///
+    /// ```no_run
+    /// # use crossterm_winapi::Console;
+    /// # fn main() -> std::io::Result<()> {
+    /// let main_screen = Console::new()?;
+    /// let alternate_screen = Console::create_alternate()?;
+    ///
+    /// alternate_screen.activate()?;
+    /// // draw on the alternate screen ...
+    /// main_screen.activate()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Wraps the underlying function call: [CreateConsoleScreenBuffer]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/createconsolescreenbuffer]
+    pub fn create_alternate() -> Result<Console> {
+        let handle = unsafe {
+            CreateConsoleScreenBuffer(
+                GENERIC_READ | GENERIC_WRITE,
+                FILE_SHARE_READ | FILE_SHARE_WRITE,
+                ptr::null(),
+                CONSOLE_TEXTMODE_BUFFER,
+                ptr::null_mut(),
+            )
+        };
+
+        if handle == INVALID_HANDLE_VALUE {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(Console::from(Handle::owned(handle)))
+    }
+
+    /// Make the screen buffer of this `Console` the active one, so that it is displayed.
+    ///
+    /// Wraps the underlying function call: [SetConsoleActiveScreenBuffer]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsoleactivescreenbuffer]
+    pub fn activate(&self) -> Result<()> {
+        self.require_output("SetConsoleActiveScreenBuffer")?;
+
+        unsafe {
+            if !is_true(SetConsoleActiveScreenBuffer(*self.handle)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
     }
 
     /// Sets the attributes of characters written to the console screen buffer by the WriteFile or WriteConsole function, or echoed by the ReadFile or ReadConsole function.
@@ -38,6 +207,8 @@ impl Console {
     /// Wraps the underlying function call: [SetConsoleTextAttribute]
     /// link: [https://docs.microsoft.com/en-us/windows/console/setconsoletextattribute]
     pub fn set_text_attribute(&self, value: u16) -> Result<()> {
+        self.require_output("SetConsoleTextAttribute")?;
+
         unsafe {
             if !is_true(SetConsoleTextAttribute(*self.handle, value)) {
                 return Err(Error::last_os_error());
@@ -46,11 +217,242 @@ impl Console {
         Ok(())
     }
 
-    /// Sets the current size and position of a console screen buffer's window.
+    /// Sets the foreground and background color of characters written after this call to the console colors nearest to the given RGB colors.
+    ///
+    /// Each color is quantized with `ConsoleColor::from_rgb`, which can also be used directly to reuse the same mapping.
+    /// Only the color bits are set, other attributes like `COMMON_LVB_UNDERSCORE` are cleared.
     ///
     /// Wraps the underlying function call: [SetConsoleTextAttribute]
     /// link: [https://docs.microsoft.com/en-us/windows/console/setconsoletextattribute]
+    pub fn set_text_attribute_rgb(&self, fg: (u8, u8, u8), bg: (u8, u8, u8)) -> Result<()> {
+        self.set_text_attribute(attributes(
+            ConsoleColor::from_rgb(fg.0, fg.1, fg.2),
+            ConsoleColor::from_rgb(bg.0, bg.1, bg.2),
+        ))
+    }
+
+    /// Sets the foreground color of characters written after this call, keeping the background color and other attributes.
+    ///
+    /// The current attributes are read back first, only the 4 foreground bits, intensity included, are replaced.
+    pub fn set_foreground(&self, color: ConsoleColor) -> Result<()> {
+        let current = self.current_attributes()?;
+        self.set_text_attribute((current & !0x000F) | color.foreground_bits())
+    }
+
+    /// Sets the background color of characters written after this call, keeping the foreground color and other attributes.
+    ///
+    /// The current attributes are read back first, only the 4 background bits, intensity included, are replaced.
+    pub fn set_background(&self, color: ConsoleColor) -> Result<()> {
+        let current = self.current_attributes()?;
+        self.set_text_attribute((current & !0x00F0) | color.background_bits())
+    }
+
+    /// Resets the attributes of characters written after this call to the conventional default of the console, grey on black.
+    ///
+    /// This is `FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE` regardless of the attributes the console started with,
+    /// use `attribute_guard` to go back to those instead.
+    pub fn reset_attributes(&self) -> Result<()> {
+        self.set_text_attribute(attributes(ConsoleColor::Grey, ConsoleColor::Black))
+    }
+
+    /// Retrieves the attributes that are applied to characters written to the console screen buffer.
+    ///
+    /// This is the `wAttributes` value as set by `set_text_attribute`.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn current_attributes(&self) -> Result<u16> {
+        Ok(self.screen_buffer_info()?.attributes())
+    }
+
+    /// Moves the cursor to the given position in the screen buffer.
+    ///
+    /// The position must lie within the screen buffer, otherwise an `InvalidInput` error holding `ConsoleError::InvalidCoord` is returned.
+    ///
+    /// Wraps the underlying function call: [SetConsoleCursorPosition]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolecursorposition]
+    pub fn set_cursor_position(&self, pos: Coord) -> Result<()> {
+        self.require_output("SetConsoleCursorPosition")?;
+
+        let size = self.screen_buffer_info()?.buffer_size();
+
+        if pos.x < 0 || pos.y < 0 || pos.x >= size.width || pos.y >= size.height {
+            return Err(ConsoleError::InvalidCoord(pos).into());
+        }
+
+        unsafe {
+            if !is_true(SetConsoleCursorPosition(*self.handle, COORD::from(pos))) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the current cursor position in the screen buffer.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn cursor_position(&self) -> Result<Coord> {
+        Ok(self.screen_buffer_info()?.cursor_pos())
+    }
+
+    /// Sets the text attributes to `value` and returns a guard that restores the current attributes when it is dropped.
+    /// The guard holds a duplicate of the handle, so it can outlive this `Console`.
+    ///
+    /// ```no_run
+    /// # use crossterm_winapi::Console;
+    /// let console = Console::new().unwrap();
+    /// {
+    ///     let _guard = console.attribute_guard(0x0004).unwrap();
+    ///     console.write_char_buffer(b"red text").unwrap();
+    /// }
+    /// // the original attributes are restored here
+    /// ```
+    pub fn attribute_guard(&self, value: u16) -> Result<AttributeGuard> {
+        let original = self.current_attributes()?;
+        self.set_text_attribute(value)?;
+
+        Ok(AttributeGuard::new(self.handle.try_clone()?, original))
+    }
+
+    /// Retrieves information about the screen buffer of this console, like the buffer size, cursor position, attributes and window.
+    ///
+    /// Use this when multiple of those values are needed, so that they are all fetched by a single call.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn screen_buffer_info(&self) -> Result<ScreenBufferInfo> {
+        self.require_output("GetConsoleScreenBufferInfo")?;
+
+        let mut csbi = ScreenBufferInfo::new();
+
+        unsafe {
+            if !is_true(GetConsoleScreenBufferInfo(*self.handle, &mut csbi.0)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(csbi)
+    }
+
+    /// Retrieves the input mode of an input handle or the output mode of an output handle.
+    ///
+    /// See `ConsoleMode` for the named flags, like `ConsoleMode::ENABLE_VIRTUAL_TERMINAL_PROCESSING`.
+    ///
+    /// Wraps the underlying function call: [GetConsoleMode]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolemode]
+    pub fn mode(&self) -> Result<u32> {
+        ConsoleMode::from(*self.handle).mode()
+    }
+
+    /// Sets the input mode of an input handle or the output mode of an output handle.
+    ///
+    /// See `ConsoleMode` for the named flags, like `ConsoleMode::ENABLE_VIRTUAL_TERMINAL_PROCESSING`.
+    ///
+    /// Wraps the underlying function call: [SetConsoleMode]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolemode]
+    pub fn set_mode(&self, mode: u32) -> Result<()> {
+        ConsoleMode::from(*self.handle).set_mode(mode)
+    }
+
+    /// Checks whether this console can process virtual terminal sequences, like ANSI escape codes.
+    ///
+    /// This tries to enable `ENABLE_VIRTUAL_TERMINAL_PROCESSING` and restores the previous mode afterwards,
+    /// so the handle must be an output handle. Any failure is treated as not supported.
+    /// The result is cached, so the mode is only changed the first time this is called on this `Console`.
+    pub fn supports_virtual_terminal(&self) -> bool {
+        if let Some(supported) = self.supports_vt.get() {
+            return supported;
+        }
+
+        let supported = match self.mode() {
+            Ok(mode) if mode & ConsoleMode::ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0 => true,
+            Ok(mode) => {
+                let supported = self
+                    .set_mode(mode | ConsoleMode::ENABLE_VIRTUAL_TERMINAL_PROCESSING)
+                    .is_ok();
+                if supported {
+                    let _ = self.set_mode(mode);
+                }
+                supported
+            }
+            Err(_) => false,
+        };
+
+        self.supports_vt.set(Some(supported));
+        supported
+    }
+
+    /// Tells which virtual terminal sequences this console understands, based on the running Windows build.
+    ///
+    /// When this console can't process virtual terminal sequences at all, see `supports_virtual_terminal`,
+    /// or the build number couldn't be retrieved, nothing is reported as supported.
+    pub fn vt_capabilities(&self) -> VtCaps {
+        match vt::build_number() {
+            Some(build) if self.supports_virtual_terminal() => VtCaps::for_build(build),
+            _ => VtCaps::default(),
+        }
+    }
+
+    /// Enables raw mode by disabling line input, echo input and processed input.
+    ///
+    /// Input is then reported key by key, without echoing it and without the system handling CTRL+C.
+    /// The returned guard restores the previous mode when it is dropped, the handle must be an input handle.
+    /// The guard holds a duplicate of the handle, so it can outlive this `Console`.
+    pub fn enter_raw_mode(&self) -> Result<RawModeGuard> {
+        const NOT_RAW_MODE_MASK: u32 = ConsoleMode::ENABLE_LINE_INPUT
+            | ConsoleMode::ENABLE_ECHO_INPUT
+            | ConsoleMode::ENABLE_PROCESSED_INPUT;
+
+        let original_mode = self.mode()?;
+        self.set_mode(original_mode & !NOT_RAW_MODE_MASK)?;
+
+        Ok(RawModeGuard::new(self.handle.try_clone()?, original_mode))
+    }
+
+    /// Enables reporting mouse events in the input buffer, the handle must be an input handle.
+    ///
+    /// Besides setting `ENABLE_MOUSE_INPUT`, this disables quick edit mode, because the console would otherwise
+    /// use mouse drags to select text instead of reporting them. The quick edit flag is only applied when
+    /// `ENABLE_EXTENDED_FLAGS` is set as well, so that flag is set too.
+    ///
+    /// The returned guard restores the previous mode when it is dropped, including the quick edit mode
+    /// if it was reported in the previous mode.
+    /// The guard holds a duplicate of the handle, so it can outlive this `Console`.
+    pub fn enable_mouse_capture(&self) -> Result<MouseCaptureGuard> {
+        self.require_input("SetConsoleMode")?;
+
+        let original_mode = self.mode()?;
+        self.set_mode(
+            (original_mode | ConsoleMode::ENABLE_MOUSE_INPUT | ConsoleMode::ENABLE_EXTENDED_FLAGS)
+                & !ConsoleMode::ENABLE_QUICK_EDIT_MODE,
+        )?;
+
+        Ok(MouseCaptureGuard::new(
+            self.handle.try_clone()?,
+            original_mode,
+        ))
+    }
+
+    /// Sets the current size and position of a console screen buffer's window.
+    ///
+    /// With `absolute` the rectangle holds the new corners of the window, otherwise it holds the number of cells every side moves by.
+    ///
+    /// An absolute rectangle is validated before the call: `right` must not be smaller than `left`,
+    /// `bottom` must not be smaller than `top`, and the resulting window must fit within
+    /// `largest_window_size()`. Otherwise an error of kind `InvalidInput` is returned.
+    /// A relative rectangle is passed on as is, its sides can be negative to move them up or left.
+    ///
+    /// Wraps the underlying function call: [SetConsoleWindowInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolewindowinfo]
     pub fn set_console_info(&self, absolute: bool, rect: WindowPositions) -> Result<()> {
+        self.require_output("SetConsoleWindowInfo")?;
+
+        if absolute {
+            self.check_window_rect(rect)?;
+        }
+
         let absolute = match absolute {
             true => 1,
             false => 0,
@@ -66,16 +468,53 @@ impl Console {
         Ok(())
     }
 
+    /// Checks that `rect` is a valid window on the screen, before setting the window to it.
+    fn check_window_rect(&self, rect: WindowPositions) -> Result<()> {
+        if rect.right < rect.left || rect.bottom < rect.top {
+            return Err(ConsoleError::InvalidRegion(rect).into());
+        }
+
+        let largest = self.largest_window_size();
+        if largest.x == 0 && largest.y == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let width = i32::from(rect.right) - i32::from(rect.left) + 1;
+        let height = i32::from(rect.bottom) - i32::from(rect.top) + 1;
+
+        if width > i32::from(largest.x) || height > i32::from(largest.y) {
+            // a window spanning the whole i16 range is wider than `i16::MAX`, but also too large for any screen.
+            let clamp = |axis: i32| axis.min(i32::from(i16::MAX)) as i16;
+
+            return Err(ConsoleError::WindowTooLarge {
+                size: Coord::new(clamp(width), clamp(height)),
+                largest,
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
     /// Writes a character to the console screen buffer a specified number of times, beginning at the specified coordinates
     ///
+    /// The character is passed as a single ANSI byte, so anything outside of ASCII will be mangled.
+    /// Prefer `fill_with_character_wide` which supports the full range of UTF-16 characters.
+    ///
+    /// The cells to fill must lie within the screen buffer: a start location outside of it results in an `InvalidInput` error
+    /// holding `ConsoleError::InvalidCoord`, and a count that reaches beyond the end of the buffer in one holding `ConsoleError::FillOutOfRange`.
+    ///
     /// Wraps the underlying function call: [FillConsoleOutputCharacterA]
     /// link: [https://docs.microsoft.com/en-us/windows/console/fillconsoleoutputcharacter]
-    pub fn fill_whit_character(
+    pub fn fill_with_character(
         &self,
         start_location: Coord,
         cells_to_write: u32,
         filling_char: char,
     ) -> Result<u32> {
+        self.require_output("FillConsoleOutputCharacterA")?;
+        self.check_fill_range(start_location, cells_to_write)?;
+
         let mut chars_written = 0;
         unsafe {
             // fill the cells in console with blanks
@@ -93,16 +532,99 @@ impl Console {
         }
     }
 
+    /// Checks that `cells_to_write` cells starting at `start` lie within the screen buffer, before filling them.
+    fn check_fill_range(&self, start: Coord, cells_to_write: u32) -> Result<()> {
+        let size = self.screen_buffer_info()?.buffer_size();
+
+        if start.x < 0 || start.y < 0 || start.x >= size.width || start.y >= size.height {
+            return Err(ConsoleError::InvalidCoord(start).into());
+        }
+
+        let capacity = size.width as u64 * size.height as u64;
+        let offset = start.y as u64 * size.width as u64 + start.x as u64;
+
+        if offset + u64::from(cells_to_write) > capacity {
+            return Err(ConsoleError::FillOutOfRange {
+                start,
+                count: cells_to_write,
+                buffer_size: Coord::new(size.width, size.height),
+            }
+            .into());
+        }
+
+        Ok(())
+    }
+
+    /// Writes a character to the console screen buffer a specified number of times, beginning at the specified coordinates.
+    ///
+    /// This is the recommended way to fill cells, because the character is written as UTF-16 instead of a single ANSI byte.
+    /// Characters that need a surrogate pair (above U+FFFF) can not be written into a single cell, for those a `CharSpansMultipleCells` error is returned.
+    /// The same goes for East Asian wide characters, which are displayed two cells wide, see `char_cell_width`:
+    /// filling cells with them would not match the number of cells they are displayed in.
+    ///
+    /// Like `fill_with_character` the cells to fill must lie within the screen buffer.
+    ///
+    /// Wraps the underlying function call: [FillConsoleOutputCharacterW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/fillconsoleoutputcharacter]
+    pub fn fill_with_character_wide(
+        &self,
+        start_location: Coord,
+        cells_to_write: u32,
+        filling_char: char,
+    ) -> Result<u32> {
+        self.require_output("FillConsoleOutputCharacterW")?;
+
+        let utf16 = encode_single_utf16(filling_char)?;
+        if char_cell_width(filling_char) > 1 {
+            return Err(spans_multiple_cells(filling_char));
+        }
+        self.check_fill_range(start_location, cells_to_write)?;
+
+        let mut chars_written = 0;
+        unsafe {
+            if !is_true(FillConsoleOutputCharacterW(
+                *self.handle,
+                utf16,
+                cells_to_write,
+                COORD::from(start_location),
+                &mut chars_written,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(chars_written)
+    }
+
+    /// Writes a character to the console screen buffer a specified number of times, beginning at the specified coordinates
+    ///
+    /// Like `fill_with_character` the cells to fill must lie within the screen buffer.
+    #[deprecated(note = "use fill_with_character")]
+    pub fn fill_whit_character(
+        &self,
+        start_location: Coord,
+        cells_to_write: u32,
+        filling_char: char,
+    ) -> Result<u32> {
+        self.fill_with_character(start_location, cells_to_write, filling_char)
+    }
+
     /// Sets the character attributes for a specified number of character cells, beginning at the specified coordinates in a screen buffer.
     ///
+    /// Like `fill_with_character` the cells to fill must lie within the screen buffer,
+    /// so filling the characters and the attributes of the same cells fails or succeeds for both.
+    ///
     /// Wraps the underlying function call: [FillConsoleOutputAttribute]
     /// link: [https://docs.microsoft.com/en-us/windows/console/fillconsoleoutputattribute]
-    pub fn fill_whit_attribute(
+    pub fn fill_with_attribute(
         &self,
         start_location: Coord,
         cells_to_write: u32,
         dw_attribute: u16,
     ) -> Result<u32> {
+        self.require_output("FillConsoleOutputAttribute")?;
+        self.check_fill_range(start_location, cells_to_write)?;
+
         let mut cells_written = 0;
         // Get the position of the current console window
         unsafe {
@@ -120,81 +642,1520 @@ impl Console {
         Ok(cells_written)
     }
 
-    /// Retrieves the size of the largest possible console window, based on the current text and the size of the display.
+    /// Sets the character attributes for a specified number of character cells, beginning at the specified coordinates in a screen buffer.
+    #[deprecated(note = "use fill_with_attribute")]
+    pub fn fill_whit_attribute(
+        &self,
+        start_location: Coord,
+        cells_to_write: u32,
+        dw_attribute: u16,
+    ) -> Result<u32> {
+        self.fill_with_attribute(start_location, cells_to_write, dw_attribute)
+    }
+
+    /// Clears the whole screen buffer and moves the cursor to the upper-left corner.
     ///
-    /// Wraps the underlying function call: [GetLargestConsoleWindowSize]
-    /// link: [https://docs.microsoft.com/en-us/windows/console/getlargestconsolewindowsize]
-    pub fn largest_window_size(&self) -> Coord {
-        Coord::from(unsafe { GetLargestConsoleWindowSize(*self.handle) })
+    /// The cells are filled with blanks in the current attributes.
+    pub fn clear(&self) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let size = info.buffer_size();
+        let cells = size.width.max(0) as u32 * size.height.max(0) as u32;
+
+        self.clear_cells(Coord::ORIGIN, cells, info.attributes())?;
+        self.set_cursor_position(Coord::ORIGIN)
     }
 
-    /// Writes a character string to a console screen buffer beginning at the current cursor location.
+    /// Clears the cells within `rect`, edges included, the cursor is not moved.
     ///
-    /// Wraps the underlying function call: [WriteConsoleW]
-    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsole]
-    pub fn write_char_buffer(&self, buf: &[u8]) -> Result<usize> {
-        // get string from u8[] and parse it to an c_str
-        let utf8 = match str::from_utf8(buf) {
-            Ok(string) => string,
-            Err(_) => {
-                return Err(io::Error::new(
-                    io::ErrorKind::Other,
-                    "Could not parse to utf8 string",
-                ));
-            }
+    /// The cells are filled with blanks in the current attributes.
+    /// If `rect` doesn't lie within the screen buffer an error of kind `InvalidInput` is returned.
+    pub fn clear_region(&self, rect: WindowPositions) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let size = info.buffer_size();
+        let buffer = WindowPositions {
+            left: 0,
+            right: size.width - 1,
+            bottom: size.height - 1,
+            top: 0,
         };
 
-        let utf16: Vec<u16> = utf8.encode_utf16().collect();
-        let utf16_ptr: *const c_void = utf16.as_ptr() as *const _ as *const c_void;
+        if !buffer.contains(Coord::new(rect.left, rect.top))
+            || !buffer.contains(Coord::new(rect.right, rect.bottom))
+            || rect.right < rect.left
+            || rect.bottom < rect.top
+        {
+            return Err(ConsoleError::InvalidRegion(rect).into());
+        }
 
-        let mut cells_written: u32 = 0;
-        // write to console
-        unsafe {
-            if !is_true(WriteConsoleW(
-                *self.handle,
-                utf16_ptr,
-                utf16.len() as u32,
-                &mut cells_written,
-                NULL,
-            )) {
-                return Err(io::Error::last_os_error());
-            }
+        for y in rect.top..=rect.bottom {
+            self.clear_cells(
+                Coord::new(rect.left, y),
+                rect.width() as u32,
+                info.attributes(),
+            )?;
         }
-        Ok(utf8.as_bytes().len())
+
+        Ok(())
     }
 
-    pub fn read_single_input_event(&self) -> Result<Option<InputRecord>> {
-        let buf_len = self.number_of_console_input_events()?;
+    /// Clears the line the cursor is on, the cursor is not moved.
+    ///
+    /// The cells are filled with blanks in the current attributes.
+    pub fn clear_line(&self) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let cursor = info.cursor_pos();
 
-        // Fast-skipping all the code below if there is nothing to read at all
-        if buf_len == 0 {
-            return Ok(None);
-        }
+        self.clear_cells(
+            Coord::new(0, cursor.y),
+            info.buffer_size().width.max(0) as u32,
+            info.attributes(),
+        )
+    }
 
-        let mut buf: Vec<INPUT_RECORD> = Vec::with_capacity(1);
-        let mut size = 0;
+    /// Clears the line the cursor is on from the cursor up to the end of the line, the cursor is not moved.
+    ///
+    /// The cells are filled with blanks in the current attributes.
+    pub fn clear_to_line_end(&self) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let cursor = info.cursor_pos();
 
-        let a = self.read_input(&mut buf, 1, &mut size)?.1[0].to_owned();
+        self.clear_cells(
+            cursor,
+            (info.buffer_size().width - cursor.x).max(0) as u32,
+            info.attributes(),
+        )
+    }
 
-        // read single input event
-        Ok(Some(a))
+    /// Clears the line the cursor is on from the start of the line up to and including the cursor, the cursor is not moved.
+    ///
+    /// The cells are filled with blanks in the current attributes.
+    pub fn clear_to_line_start(&self) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let cursor = info.cursor_pos();
+
+        self.clear_cells(
+            Coord::new(0, cursor.y),
+            (cursor.x + 1).max(0) as u32,
+            info.attributes(),
+        )
     }
 
-    pub fn read_console_input(&self) -> Result<(u32, Vec<InputRecord>)> {
-        let buf_len = self.number_of_console_input_events()?;
+    /// Fills `count` cells, starting at `start`, with blanks in the given attributes.
+    fn clear_cells(&self, start: Coord, count: u32, attributes: u16) -> Result<()> {
+        self.fill_with_character_wide(start, count, ' ')?;
+        self.fill_with_attribute(start, count, attributes)?;
+        Ok(())
+    }
+
+    /// Moves a block of data in the screen buffer to `dest`, the area that is left behind is filled with `fill_char` and `fill_attr`.
+    ///
+    /// The `scroll_rect` is the area that is moved and `dest` the new upper-left corner of that area.
+    /// Only cells within `clip_rect` are changed, when it is `None` the whole screen buffer could be changed.
+    ///
+    /// Wraps the underlying function call: [ScrollConsoleScreenBufferW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/scrollconsolescreenbuffer]
+    pub fn scroll_buffer(
+        &self,
+        scroll_rect: WindowPositions,
+        clip_rect: Option<WindowPositions>,
+        dest: Coord,
+        fill_char: char,
+        fill_attr: u16,
+    ) -> Result<()> {
+        self.require_output("ScrollConsoleScreenBufferW")?;
+
+        let mut fill: CHAR_INFO = unsafe { zeroed() };
+        unsafe {
+            *fill.Char.UnicodeChar_mut() = encode_single_utf16(fill_char)?;
+        }
+        fill.Attributes = fill_attr;
+
+        let scroll_rect = SMALL_RECT::from(scroll_rect);
+        let clip_rect = clip_rect.map(SMALL_RECT::from);
+        let clip_rect_ptr = match clip_rect {
+            Some(ref rect) => rect as *const SMALL_RECT,
+            None => ptr::null(),
+        };
+
+        unsafe {
+            if !is_true(ScrollConsoleScreenBufferW(
+                *self.handle,
+                &scroll_rect,
+                clip_rect_ptr,
+                COORD::from(dest),
+                &fill,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Scrolls the contents of the visible window up by `lines` rows, the rows that appear at the bottom are blank.
+    ///
+    /// Only the window is changed, the rest of the screen buffer and the cursor are left alone.
+    /// `lines` is clamped to the height of the window, so larger values clear the window, and zero does nothing.
+    /// The blank rows have the current attributes.
+    pub fn scroll_up(&self, lines: u16) -> Result<()> {
+        self.scroll_window(i32::from(lines))
+    }
+
+    /// Scrolls the contents of the visible window down by `lines` rows, the rows that appear at the top are blank.
+    ///
+    /// Like `scroll_up`, only the window is changed and `lines` is clamped to the height of the window.
+    pub fn scroll_down(&self, lines: u16) -> Result<()> {
+        self.scroll_window(-i32::from(lines))
+    }
+
+    /// Moves the contents of the window up by `lines` rows, or down for negative values.
+    fn scroll_window(&self, lines: i32) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let window = info.terminal_window();
+        let height = i32::from(window.height());
+        let lines = lines.max(-height).min(height);
+
+        if lines == 0 {
+            return Ok(());
+        }
+
+        self.scroll_buffer(
+            window,
+            Some(window),
+            Coord::new(window.left, (i32::from(window.top) - lines) as i16),
+            ' ',
+            info.attributes(),
+        )
+    }
+
+    /// Reads a rectangle of character cells from the screen buffer.
+    ///
+    /// The cells are read from `read_region` into a buffer of `buffer_size`, starting at `buffer_coord` in that buffer.
+    /// The returned buffer is stored row by row, next to it the region that was actually read is returned,
+    /// this could be smaller than `read_region` when it extends beyond the screen buffer.
+    /// A `CharInfo` holds a single character, so cells that hold half of a surrogate pair are read as `U+FFFD`, use `read_string_at` to read those.
+    ///
+    /// Wraps the underlying function call: [ReadConsoleOutputW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/readconsoleoutput]
+    pub fn read_output(
+        &self,
+        buffer_size: Coord,
+        buffer_coord: Coord,
+        read_region: WindowPositions,
+    ) -> Result<(Vec<CharInfo>, WindowPositions)> {
+        self.require_output("ReadConsoleOutputW")?;
+
+        if buffer_size.x < 0 || buffer_size.y < 0 {
+            return Err(ConsoleError::InvalidSize(buffer_size).into());
+        }
+
+        let mut buf: Vec<CHAR_INFO> =
+            vec![unsafe { zeroed() }; buffer_size.x as usize * buffer_size.y as usize];
+        let mut read_region = SMALL_RECT::from(read_region);
+
+        unsafe {
+            if !is_true(ReadConsoleOutputW(
+                *self.handle,
+                buf.as_mut_ptr(),
+                COORD::from(buffer_size),
+                COORD::from(buffer_coord),
+                &mut read_region,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok((
+            buf.into_iter().map(CharInfo::from).collect(),
+            WindowPositions::from(read_region),
+        ))
+    }
+
+    /// Writes a rectangle of character cells to the screen buffer.
+    ///
+    /// The `buffer` is interpreted as a rectangle of `buffer_size` stored row by row,
+    /// the cells starting at `buffer_coord` in that buffer are written into `write_region`.
+    /// On success this returns the region that was actually written, which may be clipped to the screen buffer.
+    ///
+    /// An `InvalidInput` error is returned when `buffer` holds less cells than `buffer_size` describes.
+    /// Each cell holds a single UTF-16 code unit, for characters that need a surrogate pair, like `U+1F600`, a `CharSpansMultipleCells` error is returned.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleOutputW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsoleoutput]
+    pub fn write_output(
+        &self,
+        buffer: &[CharInfo],
+        buffer_size: Coord,
+        buffer_coord: Coord,
+        write_region: WindowPositions,
+    ) -> Result<WindowPositions> {
+        self.require_output("WriteConsoleOutputW")?;
+
+        if buffer_size.x < 0 || buffer_size.y < 0 {
+            return Err(ConsoleError::InvalidSize(buffer_size).into());
+        }
+        if buffer.len() < buffer_size.x as usize * buffer_size.y as usize {
+            return Err(ConsoleError::BufferLength {
+                len: buffer.len(),
+                size: buffer_size,
+            }
+            .into());
+        }
+
+        if let Some(cell) = buffer.iter().find(|cell| cell.ch.len_utf16() != 1) {
+            return Err(spans_multiple_cells(cell.ch));
+        }
+
+        let buf: Vec<CHAR_INFO> = buffer.iter().map(|&cell| CHAR_INFO::from(cell)).collect();
+        let mut write_region = SMALL_RECT::from(write_region);
+
+        unsafe {
+            if !is_true(WriteConsoleOutputW(
+                *self.handle,
+                buf.as_ptr(),
+                COORD::from(buffer_size),
+                COORD::from(buffer_coord),
+                &mut write_region,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(WindowPositions::from(write_region))
+    }
+
+    /// Writes a string to the cells starting at the given position, keeping the attributes of those cells.
+    ///
+    /// The cursor is not used or moved. Text that reaches the end of a row continues on the next row,
+    /// and text that reaches the end of the screen buffer is cut off.
+    /// A position outside of the screen buffer is clamped to the nearest cell inside it, see `Coord::clamp_to`.
+    /// Returns the number of UTF-16 code units that were written,
+    /// so a character that needs a surrogate pair, like `U+1F600`, counts as two and takes up two cells.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleOutputCharacterW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsoleoutputcharacter]
+    pub fn write_string_at(&self, s: &str, at: Coord) -> Result<u32> {
+        self.require_output("WriteConsoleOutputCharacterW")?;
+
+        let at = at.clamp_to(self.buffer_size()?);
+
+        let utf16: Vec<u16> = s.encode_utf16().collect();
+        let mut chars_written = 0;
+
+        unsafe {
+            if !is_true(WriteConsoleOutputCharacterW(
+                *self.handle,
+                utf16.as_ptr(),
+                utf16.len() as DWORD,
+                COORD::from(at),
+                &mut chars_written,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(chars_written)
+    }
+
+    /// Sets the attributes of the cells starting at the given position, one attribute per cell, keeping the characters of those cells.
+    ///
+    /// Like `write_string_at` this doesn't use the cursor, continues on the next row and is cut off at the end of the screen buffer,
+    /// and a position outside of the screen buffer is clamped to the nearest cell inside it.
+    /// Returns the number of cells whose attributes were written.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleOutputAttribute]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsoleoutputattribute]
+    pub fn write_attributes_at(&self, attrs: &[u16], at: Coord) -> Result<u32> {
+        self.require_output("WriteConsoleOutputAttribute")?;
+
+        let at = at.clamp_to(self.buffer_size()?);
+
+        let mut attrs_written = 0;
+
+        unsafe {
+            if !is_true(WriteConsoleOutputAttribute(
+                *self.handle,
+                attrs.as_ptr(),
+                attrs.len() as DWORD,
+                COORD::from(at),
+                &mut attrs_written,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(attrs_written)
+    }
+
+    /// Writes a string to the cells starting at the given position like `write_string_at`, and sets the attributes of those cells to `attr`.
+    ///
+    /// The attributes are written for exactly the cells that the characters were written to,
+    /// so a string that is cut off at the end of the screen buffer doesn't change the attributes of any other cell.
+    /// Returns the number of UTF-16 code units that were written.
+    ///
+    /// Wraps the underlying function calls: [WriteConsoleOutputCharacterW, WriteConsoleOutputAttribute]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsoleoutputcharacter]
+    pub fn write_styled_at(&self, s: &str, at: Coord, attr: u16) -> Result<u32> {
+        let chars_written = self.write_string_at(s, at)?;
+        self.write_attributes_at(&vec![attr; chars_written as usize], at)?;
+
+        Ok(chars_written)
+    }
+
+    /// Reads the characters of `len` cells starting at the given position.
+    ///
+    /// Like `write_string_at` this doesn't use the cursor, continues on the next row and stops at the end of the screen buffer,
+    /// so the returned string could be shorter than `len` characters.
+    /// Each cell holds a single UTF-16 code unit, so a surrogate pair written by `write_string_at` is read back as one character from two cells.
+    /// A cell that holds only half of a surrogate pair, because `len` ends in the middle of it, is replaced with `U+FFFD`.
+    ///
+    /// Wraps the underlying function call: [ReadConsoleOutputCharacterW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/readconsoleoutputcharacter]
+    pub fn read_string_at(&self, at: Coord, len: u32) -> Result<String> {
+        self.require_output("ReadConsoleOutputCharacterW")?;
+
+        let mut utf16 = vec![0u16; len as usize];
+        let mut chars_read = 0;
+
+        unsafe {
+            if !is_true(ReadConsoleOutputCharacterW(
+                *self.handle,
+                utf16.as_mut_ptr(),
+                len,
+                COORD::from(at),
+                &mut chars_read,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        utf16.truncate(chars_read as usize);
+        Ok(String::from_utf16_lossy(&utf16))
+    }
+
+    /// Reads the attributes of `len` cells starting at the given position.
+    ///
+    /// Like `read_string_at` this stops at the end of the screen buffer, so fewer than `len` attributes could be returned.
+    ///
+    /// Wraps the underlying function call: [ReadConsoleOutputAttribute]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/readconsoleoutputattribute]
+    pub fn read_attributes_at(&self, at: Coord, len: u32) -> Result<Vec<u16>> {
+        self.require_output("ReadConsoleOutputAttribute")?;
+
+        let mut attrs = vec![0u16; len as usize];
+        let mut attrs_read = 0;
+
+        unsafe {
+            if !is_true(ReadConsoleOutputAttribute(
+                *self.handle,
+                attrs.as_mut_ptr(),
+                len,
+                COORD::from(at),
+                &mut attrs_read,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        attrs.truncate(attrs_read as usize);
+        Ok(attrs)
+    }
+
+    /// Takes a snapshot of the whole screen buffer, including the cursor position and the current attributes.
+    ///
+    /// Use `restore_screen` to write the snapshot back, for example after showing a temporary overlay.
+    pub fn save_screen(&self) -> Result<ScreenSnapshot> {
+        let info = self.screen_buffer_info()?;
+        let size = info.buffer_size();
+        let mut cells =
+            Vec::with_capacity(size.width.max(0) as usize * size.height.max(0) as usize);
+
+        for (top, rows) in row_chunks(size.width, size.height) {
+            let (chunk, _) = self.read_output(
+                Coord::new(size.width, rows),
+                Coord::ORIGIN,
+                WindowPositions {
+                    left: 0,
+                    right: size.width - 1,
+                    bottom: top + rows - 1,
+                    top,
+                },
+            )?;
+            cells.extend(chunk);
+        }
+
+        Ok(ScreenSnapshot {
+            size: Coord::new(size.width, size.height),
+            cells,
+            cursor_position: info.cursor_pos(),
+            attributes: info.attributes(),
+        })
+    }
+
+    /// Writes a snapshot taken with `save_screen` back to the screen buffer and restores the cursor position and attributes.
+    ///
+    /// When the screen buffer was resized in between, only the part of the snapshot that fits in the current screen buffer
+    /// is restored and the cursor position is clamped to the screen buffer.
+    pub fn restore_screen(&self, snapshot: &ScreenSnapshot) -> Result<()> {
+        let size = self.screen_buffer_info()?.buffer_size();
+        let width = snapshot.size.x.min(size.width);
+        let height = snapshot.size.y.min(size.height);
+
+        for (top, rows) in row_chunks(snapshot.size.x, height) {
+            let start = top as usize * snapshot.size.x as usize;
+            self.write_output(
+                &snapshot.cells[start..],
+                Coord::new(snapshot.size.x, rows),
+                Coord::ORIGIN,
+                WindowPositions {
+                    left: 0,
+                    right: width - 1,
+                    bottom: top + rows - 1,
+                    top,
+                },
+            )?;
+        }
+
+        self.set_cursor_position(Coord::new(
+            snapshot.cursor_position.x.min(size.width - 1),
+            snapshot.cursor_position.y.min(size.height - 1),
+        ))?;
+        self.set_text_attribute(snapshot.attributes)
+    }
+
+    /// Changes the size of the screen buffer.
+    ///
+    /// The screen buffer can not be smaller than the console window, so shrinking the buffer below the current window size fails.
+    /// Shrink the window first with `set_console_info` before shrinking the buffer,
+    /// and likewise grow the buffer first before growing the window beyond it.
+    ///
+    /// Wraps the underlying function call: [SetConsoleScreenBufferSize]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolescreenbuffersize]
+    pub fn set_screen_buffer_size(&self, size: Coord) -> Result<()> {
+        self.require_output("SetConsoleScreenBufferSize")?;
+
+        unsafe {
+            if !is_true(SetConsoleScreenBufferSize(*self.handle, COORD::from(size))) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Grows the console window to the largest size that fits on the display, see `largest_window_size`.
+    ///
+    /// The screen buffer is grown first when it is smaller than that size, because the window can't be larger than the buffer.
+    /// The window keeps its position in the buffer as long as it fits.
+    /// This `Console` remembers the size the buffer had before, so `restore` can shrink it back.
+    pub fn maximize(&self) -> Result<()> {
+        let largest = self.largest_window_size();
+        if largest.x == 0 && largest.y == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        let buffer = self.buffer_size()?;
+        self.set_window_size(largest)?;
+
+        // keep the size from before the first `maximize`, maximizing twice doesn't grow the buffer again.
+        if self.unmaximized_buffer.get().is_none() {
+            self.unmaximized_buffer.set(Some(buffer));
+        }
+
+        Ok(())
+    }
+
+    /// Changes the console window to the given size, `x` being the width and `y` the height, for example to undo `maximize`.
+    ///
+    /// Like `maximize` the screen buffer is grown first when it is smaller than `size`.
+    /// After `maximize` was called on this `Console`, the buffer is shrunk back to the size it had before,
+    /// but never below `size`, since the buffer can't be smaller than the window.
+    pub fn restore(&self, size: Coord) -> Result<()> {
+        self.set_window_size(size)?;
+
+        if let Some(buffer) = self.unmaximized_buffer.take() {
+            let shrunk = Coord::new(buffer.x.max(size.x), buffer.y.max(size.y));
+
+            if shrunk != self.buffer_size()? {
+                self.set_screen_buffer_size(shrunk)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the size of the window, growing the screen buffer first if needed and moving the window so it stays inside the buffer.
+    ///
+    /// The size is checked against `largest_window_size` before the buffer is grown,
+    /// and the buffer is set back to its original size when setting the window fails.
+    fn set_window_size(&self, size: Coord) -> Result<()> {
+        if size.x < 1 || size.y < 1 {
+            return Err(ConsoleError::InvalidSize(size).into());
+        }
+
+        let largest = self.largest_window_size();
+        if largest.x == 0 && largest.y == 0 {
+            return Err(Error::last_os_error());
+        }
+        if size.x > largest.x || size.y > largest.y {
+            return Err(ConsoleError::WindowTooLarge { size, largest }.into());
+        }
+
+        let info = self.screen_buffer_info()?;
+        let buffer = info.buffer_size();
+        let buffer = Coord::new(buffer.width, buffer.height);
+        let window = info.terminal_window();
+
+        let grown = Coord::new(buffer.x.max(size.x), buffer.y.max(size.y));
+        if grown != buffer {
+            self.set_screen_buffer_size(grown)?;
+        }
+
+        let left = window.left.min(grown.x - size.x);
+        let top = window.top.min(grown.y - size.y);
+
+        let result = self.set_console_info(
+            true,
+            WindowPositions {
+                left,
+                right: left + size.x - 1,
+                bottom: top + size.y - 1,
+                top,
+            },
+        );
+
+        if result.is_err() && grown != buffer {
+            // the window didn't change, so it still fits in the original buffer.
+            let _ = self.set_screen_buffer_size(buffer);
+        }
+
+        result
+    }
+
+    /// Retrieves the position of the visible console window in the screen buffer, as it is passed to `set_console_info`.
+    ///
+    /// ```no_run
+    /// # use crossterm_winapi::Console;
+    /// # fn main() -> std::io::Result<()> {
+    /// let console = Console::new()?;
+    ///
+    /// // scroll the window down by a row
+    /// let mut rect = console.window_rect()?;
+    /// rect.top += 1;
+    /// rect.bottom += 1;
+    /// console.set_console_info(true, rect)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn window_rect(&self) -> Result<WindowPositions> {
+        Ok(self.screen_buffer_info()?.terminal_window())
+    }
+
+    /// Retrieves the size of the visible console window in character cells, `x` being the width and `y` the height.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn window_size(&self) -> Result<Coord> {
+        let window = self.screen_buffer_info()?.terminal_window();
+        Ok(Coord::new(window.width(), window.height()))
+    }
+
+    /// Retrieves the number of columns of the visible console window.
+    pub fn num_columns(&self) -> Result<usize> {
+        Ok(self.window_size()?.x.max(0) as usize)
+    }
+
+    /// Retrieves the number of rows of the visible console window.
+    pub fn num_rows(&self) -> Result<usize> {
+        Ok(self.window_size()?.y.max(0) as usize)
+    }
+
+    /// Retrieves the size of the screen buffer in character cells, `x` being the width and `y` the height.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfo]
+    pub fn buffer_size(&self) -> Result<Coord> {
+        let size = self.screen_buffer_info()?.buffer_size();
+        Ok(Coord::new(size.width, size.height))
+    }
+
+    /// Retrieves the size of the largest possible console window, based on the current text and the size of the display.
+    ///
+    /// On systems with multiple monitors the size is calculated for a single display,
+    /// which is not necessarily the monitor the console window is on. Use `largest_window_size_on` for a specific monitor.
+    ///
+    /// Wraps the underlying function call: [GetLargestConsoleWindowSize]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getlargestconsolewindowsize]
+    pub fn largest_window_size(&self) -> Coord {
+        Coord::from(unsafe { GetLargestConsoleWindowSize(*self.handle) })
+    }
+
+    /// Calculates the size, in cells, of the largest console window that fits on the work area of the given monitor.
+    ///
+    /// With `None` the monitor that contains the largest part of the console window is used,
+    /// when the console has no window an error of kind `NotFound` is returned.
+    /// The window frame and the vertical scroll bar are subtracted from the work area before it is divided by the current font size.
+    ///
+    /// Wraps the underlying function calls: [GetMonitorInfoW, MonitorFromWindow]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/winuser/nf-winuser-getmonitorinfow]
+    pub fn largest_window_size_on(&self, monitor: Option<HMONITOR>) -> Result<Coord> {
+        let window = self.window_handle();
+
+        let monitor = match (monitor, window) {
+            (Some(monitor), _) => monitor,
+            (None, Some(window)) => unsafe { MonitorFromWindow(window, MONITOR_DEFAULTTONEAREST) },
+            (None, None) => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "The console has no window to find the monitor of",
+                ))
+            }
+        };
+
+        let mut info: MONITORINFO = unsafe { zeroed() };
+        info.cbSize = size_of::<MONITORINFO>() as u32;
+
+        if !is_true(unsafe { GetMonitorInfoW(monitor, &mut info) }) {
+            return Err(Error::last_os_error());
+        }
+
+        // the size of the frame around the client area, for the style of the console window if there is one.
+        let style = match window {
+            Some(window) => unsafe { GetWindowLongW(window, GWL_STYLE) as u32 },
+            None => WS_OVERLAPPEDWINDOW,
+        };
+
+        let mut frame = RECT {
+            left: 0,
+            top: 0,
+            right: 0,
+            bottom: 0,
+        };
+
+        if !is_true(unsafe { AdjustWindowRectEx(&mut frame, style, FALSE, 0) }) {
+            return Err(Error::last_os_error());
+        }
+
+        let scroll_bar = unsafe { GetSystemMetrics(SM_CXVSCROLL) };
+        let work = info.rcWork;
+
+        window_cells(
+            (work.right - work.left) - (frame.right - frame.left) - scroll_bar,
+            (work.bottom - work.top) - (frame.bottom - frame.top),
+            self.font()?.size,
+        )
+    }
+
+    /// Retrieves the handle of the window that shows the console, for example to change its icon or position.
+    ///
+    /// Returns `None` when the console has no window, like for a process that is started without one.
+    /// The window belongs to the console host, not to this process.
+    ///
+    /// Wraps the underlying function call: [GetConsoleWindow]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolewindow]
+    pub fn window_handle(&self) -> Option<HWND> {
+        let window = unsafe { GetConsoleWindow() };
+
+        if window.is_null() {
+            None
+        } else {
+            Some(window)
+        }
+    }
+
+    /// Retrieves information about the current console font.
+    ///
+    /// Wraps the underlying function call: [GetCurrentConsoleFontEx]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getcurrentconsolefontex]
+    pub fn font(&self) -> Result<FontInfo> {
+        self.require_output("GetCurrentConsoleFontEx")?;
+
+        let mut font_info: CONSOLE_FONT_INFOEX = unsafe { zeroed() };
+        font_info.cbSize = size_of::<CONSOLE_FONT_INFOEX>() as u32;
+
+        unsafe {
+            if !is_true(GetCurrentConsoleFontEx(*self.handle, FALSE, &mut font_info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(FontInfo::from(font_info))
+    }
+
+    /// Sets the current console font.
+    ///
+    /// The face name is truncated to 31 UTF-16 code units.
+    ///
+    /// Wraps the underlying function call: [SetCurrentConsoleFontEx]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setcurrentconsolefontex]
+    pub fn set_font(&self, info: &FontInfo) -> Result<()> {
+        self.require_output("SetCurrentConsoleFontEx")?;
+
+        let mut font_info = CONSOLE_FONT_INFOEX::from(info);
+
+        unsafe {
+            if !is_true(SetCurrentConsoleFontEx(*self.handle, FALSE, &mut font_info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves information about the current text selection of the console.
+    ///
+    /// Wraps the underlying function call: [GetConsoleSelectionInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsoleselectioninfo]
+    pub fn selection_info(&self) -> Result<SelectionInfo> {
+        let mut selection_info: CONSOLE_SELECTION_INFO = unsafe { zeroed() };
+
+        unsafe {
+            if !is_true(GetConsoleSelectionInfo(&mut selection_info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(SelectionInfo::from(selection_info))
+    }
+
+    /// Retrieves the RGB values of the 16 console colors, indexed by the color value of `ConsoleColor`.
+    ///
+    /// Wraps the underlying function call: [GetConsoleScreenBufferInfoEx]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolescreenbufferinfoex]
+    pub fn color_table(&self) -> Result<[(u8, u8, u8); 16]> {
+        let info = self.screen_buffer_info_ex()?;
+
+        let mut table = [(0, 0, 0); 16];
+        for (color, &colorref) in table.iter_mut().zip(info.ColorTable.iter()) {
+            // a `COLORREF` is stored as `0x00BBGGRR`.
+            *color = (
+                colorref as u8,
+                (colorref >> 8) as u8,
+                (colorref >> 16) as u8,
+            );
+        }
+
+        Ok(table)
+    }
+
+    /// Sets the RGB values of the 16 console colors, indexed by the color value of `ConsoleColor`.
+    ///
+    /// This changes how the text that is already on the screen looks as well,
+    /// keep the result of `color_table` to restore the original palette on exit.
+    ///
+    /// Wraps the underlying function call: [SetConsoleScreenBufferInfoEx]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolescreenbufferinfoex]
+    pub fn set_color_table(&self, table: &[(u8, u8, u8); 16]) -> Result<()> {
+        let mut info = self.screen_buffer_info_ex()?;
+
+        for (colorref, &(r, g, b)) in info.ColorTable.iter_mut().zip(table.iter()) {
+            *colorref = u32::from(r) | u32::from(g) << 8 | u32::from(b) << 16;
+        }
+
+        // the window is read with an inclusive bottom right corner, but set with an exclusive one,
+        // without this the window would shrink by a row and a column.
+        info.srWindow.Right += 1;
+        info.srWindow.Bottom += 1;
+
+        unsafe {
+            if !is_true(SetConsoleScreenBufferInfoEx(*self.handle, &mut info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn screen_buffer_info_ex(&self) -> Result<CONSOLE_SCREEN_BUFFER_INFOEX> {
+        self.require_output("GetConsoleScreenBufferInfoEx")?;
+
+        let mut info: CONSOLE_SCREEN_BUFFER_INFOEX = unsafe { zeroed() };
+        info.cbSize = size_of::<CONSOLE_SCREEN_BUFFER_INFOEX>() as u32;
+
+        unsafe {
+            if !is_true(GetConsoleScreenBufferInfoEx(*self.handle, &mut info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(info)
+    }
+
+    /// Retrieves the size and visibility of the cursor.
+    ///
+    /// The size is the percentage of the character cell that is filled by the cursor, from 1 to 100.
+    ///
+    /// Wraps the underlying function call: [GetConsoleCursorInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolecursorinfo]
+    pub fn cursor_info(&self) -> Result<(u32, bool)> {
+        self.require_output("GetConsoleCursorInfo")?;
+
+        let mut cursor_info: CONSOLE_CURSOR_INFO = unsafe { zeroed() };
+
+        unsafe {
+            if !is_true(GetConsoleCursorInfo(*self.handle, &mut cursor_info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok((cursor_info.dwSize, is_true(cursor_info.bVisible)))
+    }
+
+    /// Sets the size and visibility of the cursor.
+    ///
+    /// The size is the percentage of the character cell that is filled by the cursor.
+    /// Sizes above 100 are clamped to 100, a size of 0 results in an `InvalidInput` error.
+    ///
+    /// Wraps the underlying function call: [SetConsoleCursorInfo]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolecursorinfo]
+    pub fn set_cursor_info(&self, size: u32, visible: bool) -> Result<()> {
+        self.require_output("SetConsoleCursorInfo")?;
+
+        if size == 0 {
+            return Err(ConsoleError::ValueOutOfRange {
+                argument: "cursor size",
+                value: size,
+                min: 1,
+                max: 100,
+            }
+            .into());
+        }
+
+        let cursor_info = CONSOLE_CURSOR_INFO {
+            dwSize: size.min(100),
+            bVisible: if visible { TRUE } else { FALSE },
+        };
+
+        unsafe {
+            if !is_true(SetConsoleCursorInfo(*self.handle, &cursor_info)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Generates a simple tone on the speaker, this blocks until the sound finishes.
+    ///
+    /// The frequency is in hertz and must be in the range `37..=32767`, otherwise an error of kind `InvalidInput` is returned.
+    ///
+    /// Wraps the underlying function call: [Beep]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/utilapiset/nf-utilapiset-beep]
+    pub fn beep(&self, frequency: u32, duration: Duration) -> Result<()> {
+        if !(37..=32767).contains(&frequency) {
+            return Err(ConsoleError::ValueOutOfRange {
+                argument: "beep frequency",
+                value: frequency,
+                min: 37,
+                max: 32767,
+            }
+            .into());
+        }
+
+        let millis = duration.as_millis().min(u128::from(u32::MAX)) as DWORD;
+
+        if !is_true(unsafe { Beep(frequency, millis) }) {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Sends a Ctrl+C or Ctrl+Break signal to the processes of the given process group that share this console.
+    ///
+    /// A `process_group_id` of 0 sends the signal to all processes that share the console, including the calling process.
+    /// Otherwise it is the id of the group leader of a process created with `CREATE_NEW_PROCESS_GROUP`,
+    /// note that Ctrl+C is ignored by such a group unless the processes explicitly enabled it.
+    ///
+    /// Wraps the underlying function call: [GenerateConsoleCtrlEvent]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/generateconsolectrlevent]
+    pub fn generate_ctrl_event(&self, event: CtrlEvent, process_group_id: u32) -> Result<()> {
+        let event = match event {
+            CtrlEvent::C => CTRL_C_EVENT,
+            CtrlEvent::Break => CTRL_BREAK_EVENT,
+        };
+
+        unsafe {
+            if !is_true(GenerateConsoleCtrlEvent(event, process_group_id)) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sets the title of the console window.
+    ///
+    /// Wraps the underlying function call: [SetConsoleTitleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsoletitle]
+    pub fn set_title(&self, title: &str) -> Result<()> {
+        let utf16: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+
+        unsafe {
+            if !is_true(SetConsoleTitleW(utf16.as_ptr())) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Retrieves the title of the console window.
+    ///
+    /// Titles longer than 1024 UTF-16 code units are truncated, invalid UTF-16 is replaced with `U+FFFD`.
+    ///
+    /// Wraps the underlying function call: [GetConsoleTitleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsoletitle]
+    pub fn title(&self) -> Result<String> {
+        let mut utf16 = vec![0u16; MAX_TITLE_LENGTH];
+        let length = unsafe {
+            // an empty title also returns 0, so clear the last error to be able to tell those apart.
+            SetLastError(0);
+            GetConsoleTitleW(utf16.as_mut_ptr(), utf16.len() as DWORD)
+        };
+
+        if length == 0 {
+            let error = Error::last_os_error();
+            return match error.raw_os_error() {
+                Some(0) | None => Ok(String::new()),
+                _ => Err(error),
+            };
+        }
+
+        let length = (length as usize).min(utf16.len());
+        let title = &utf16[..length];
+        let title = match title.iter().position(|&c| c == 0) {
+            Some(end) => &title[..end],
+            None => title,
+        };
+
+        Ok(String::from_utf16_lossy(title))
+    }
+
+    /// Retrieves the output code page used by the console, text written to the console is translated with this code page.
+    ///
+    /// The code page belongs to the console and not to the handle, so this applies to all screen buffers of the console.
+    ///
+    /// Wraps the underlying function call: [GetConsoleOutputCP]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsoleoutputcp]
+    pub fn output_code_page(&self) -> Result<u32> {
+        let code_page = unsafe { GetConsoleOutputCP() };
+
+        if code_page == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(code_page)
+    }
+
+    /// Retrieves the input code page used by the console, keyboard input is translated with this code page.
+    ///
+    /// Wraps the underlying function call: [GetConsoleCP]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolecp]
+    pub fn input_code_page(&self) -> Result<u32> {
+        let code_page = unsafe { GetConsoleCP() };
+
+        if code_page == 0 {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(code_page)
+    }
+
+    /// Sets the output code page used by the console, for example `65001` for UTF-8.
+    ///
+    /// Wraps the underlying function call: [SetConsoleOutputCP]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsoleoutputcp]
+    pub fn set_output_code_page(&self, code_page: u32) -> Result<()> {
+        if !is_true(unsafe { SetConsoleOutputCP(code_page) }) {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Sets the input code page used by the console, for example `65001` for UTF-8.
+    ///
+    /// Wraps the underlying function call: [SetConsoleCP]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/setconsolecp]
+    pub fn set_input_code_page(&self, code_page: u32) -> Result<()> {
+        if !is_true(unsafe { SetConsoleCP(code_page) }) {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Writes a character string to a console screen buffer beginning at the current cursor location.
+    ///
+    /// Fails with an error of kind `InvalidData` if `buf` is not valid UTF-8.
+    /// Characters that need a surrogate pair, like `U+1F600`, are passed to the console as a whole, also when the input is written in several calls.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsole]
+    pub fn write_char_buffer(&self, buf: &[u8]) -> Result<usize> {
+        self.write_char_buffer_checked(buf)?;
+        Ok(buf.len())
+    }
+
+    /// Writes a character string to a console screen buffer beginning at the current cursor location.
+    ///
+    /// Unlike `write_char_buffer` this reports how much of the input actually took effect,
+    /// which could be less than the input when the console clipped the output.
+    /// Large inputs are written in several calls of bounded size.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsole]
+    pub fn write_char_buffer_checked(&self, buf: &[u8]) -> Result<CharsWritten> {
+        // get string from u8[] and parse it to an c_str
+        let utf8 = match str::from_utf8(buf) {
+            Ok(string) => string,
+            Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+        };
+
+        self.write_utf8(utf8)
+    }
+
+    /// Writes a string to a console screen buffer beginning at the current cursor location.
+    ///
+    /// Unlike `write_char_buffer` this doesn't need to validate the input as UTF-8 first.
+    /// Returns the number of bytes of `s` that were written to the console.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsole]
+    pub fn write_str(&self, s: &str) -> Result<usize> {
+        Ok(self.write_utf8(s)?.bytes_consumed)
+    }
+
+    /// Writes a string like `write_str`, but handles every `\n` itself by moving the cursor to the start of the next row.
+    ///
+    /// With `write_str` the effect of `\n` depends on the output mode: with `ENABLE_PROCESSED_OUTPUT` it starts a new line,
+    /// without it a line feed only moves the cursor down, or is shown as a character.
+    /// This function writes the text between the line breaks with `write_str` and positions the cursor explicitly,
+    /// so the output looks the same in every mode. On the last row of the screen buffer the buffer is scrolled up by a row.
+    /// A `\r` right before a `\n` is part of the line break.
+    ///
+    /// Returns the number of bytes of `s` that were written, line breaks included.
+    pub fn write_str_translated(&self, s: &str) -> Result<usize> {
+        let mut written = 0;
+        let last = s.matches('\n').count();
+
+        for (i, line) in s.split('\n').enumerate() {
+            if i > 0 {
+                self.new_line()?;
+                // the line feed that was split off.
+                written += 1;
+            }
+
+            // only a `\r` followed by a line feed belongs to the line break, a trailing one is written as is.
+            let text = if i < last {
+                line.strip_suffix('\r').unwrap_or(line)
+            } else {
+                line
+            };
+            let line_written = self.write_str(text)?;
+
+            if line_written < text.len() {
+                return Ok(written + line_written);
+            }
+
+            written += line.len();
+        }
+
+        Ok(written)
+    }
+
+    /// Moves the cursor to the start of the next row, scrolling the screen buffer up when the cursor is on the last row.
+    fn new_line(&self) -> Result<()> {
+        let info = self.screen_buffer_info()?;
+        let size = info.buffer_size();
+        let row = info.cursor_pos().y;
+
+        if row + 1 < size.height {
+            return self.set_cursor_position(Coord::new(0, row + 1));
+        }
+
+        let buffer = WindowPositions {
+            left: 0,
+            right: size.width - 1,
+            bottom: size.height - 1,
+            top: 0,
+        };
+
+        self.scroll_buffer(buffer, None, Coord::new(0, -1), ' ', info.attributes())?;
+        self.set_cursor_position(Coord::new(0, row))
+    }
+
+    fn write_utf8(&self, utf8: &str) -> Result<CharsWritten> {
+        let utf16: Vec<u16> = utf8.encode_utf16().collect();
+        let total_written = self.write_utf16(&utf16)?;
+
+        Ok(CharsWritten {
+            bytes_consumed: utf8_len_of_utf16_prefix(utf8, total_written),
+            cells_written: u32::try_from(total_written).unwrap_or(u32::MAX),
+        })
+    }
+
+    /// Writes UTF-16 text to a console screen buffer beginning at the current cursor location,
+    /// without converting or copying it first.
+    ///
+    /// Large inputs are written in several calls of bounded size, without splitting a surrogate pair over two calls.
+    /// Returns the number of UTF-16 code units that were written, which could be less than `buf.len()`
+    /// when the console clipped the output.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsole]
+    pub fn write_utf16(&self, buf: &[u16]) -> Result<usize> {
+        self.require_output("WriteConsoleW")?;
+
+        let mut total_written = 0;
+        while total_written < buf.len() {
+            let mut chunk_end = usize::min(total_written + MAX_WRITE_LENGTH, buf.len());
+            // don't split a surrogate pair over two writes
+            if chunk_end < buf.len() && is_high_surrogate(buf[chunk_end - 1]) {
+                chunk_end -= 1;
+            }
+            let chunk = &buf[total_written..chunk_end];
+
+            let mut cells_written: u32 = 0;
+            // write to console
+            unsafe {
+                if !is_true(WriteConsoleW(
+                    *self.handle,
+                    chunk.as_ptr() as *const c_void,
+                    chunk.len() as u32,
+                    &mut cells_written,
+                    NULL,
+                )) {
+                    return Err(io::Error::last_os_error());
+                }
+            }
+
+            total_written += cells_written as usize;
+            if (cells_written as usize) < chunk.len() {
+                // the console didn't take the whole chunk, so stop here
+                break;
+            }
+        }
+
+        Ok(total_written)
+    }
+
+    /// Reads a line of text from the console input buffer, the handle must be an input handle.
+    ///
+    /// With the default input mode this blocks until the user presses Enter, while the console provides its own line editing,
+    /// like backspace and the command history. The line is returned without the line break.
+    /// At most `max_chars` UTF-16 code units are read in one call, the rest of a longer line is returned by the next call.
+    ///
+    /// Wraps the underlying function call: [ReadConsoleW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/readconsole]
+    pub fn read_line(&self, max_chars: u32) -> Result<String> {
+        self.require_input("ReadConsoleW")?;
+
+        let mut utf16 = vec![0u16; max_chars as usize];
+        let mut chars_read = 0;
+
+        unsafe {
+            if !is_true(ReadConsoleW(
+                *self.handle,
+                utf16.as_mut_ptr() as *mut c_void,
+                max_chars,
+                &mut chars_read,
+                ptr::null_mut(),
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        utf16.truncate(chars_read as usize);
+        let line = String::from_utf16_lossy(&utf16);
+        Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+    }
+
+    pub fn read_single_input_event(&self) -> Result<Option<InputRecord>> {
+        let buf_len = self.number_of_console_input_events()?;
 
         // Fast-skipping all the code below if there is nothing to read at all
         if buf_len == 0 {
-            return Ok((0, vec![]));
+            return Ok(None);
         }
 
-        let mut buf: Vec<INPUT_RECORD> = Vec::with_capacity(buf_len as usize);
+        let mut buf: Vec<INPUT_RECORD> = Vec::with_capacity(1);
         let mut size = 0;
 
-        self.read_input(&mut buf, buf_len, &mut size)
+        // read single input event
+        Ok(self
+            .read_input(&mut buf, 1, &mut size)?
+            .1
+            .into_iter()
+            .next())
+    }
+
+    /// Waits up to `timeout` for an input event to become available and reads it.
+    ///
+    /// This returns `Ok(None)` when no event arrived before the timeout elapsed.
+    /// The handle of this `Console` must be an input handle.
+    ///
+    /// Wraps the underlying function call: [WaitForSingleObject]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/synchapi/nf-synchapi-waitforsingleobject]
+    pub fn read_single_input_event_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<Option<InputRecord>> {
+        if !self.wait_for_input(timeout_to_millis(timeout))? {
+            return Ok(None);
+        }
+
+        self.read_single_input_event()
+    }
+
+    /// Waits for the next input event and decodes it into an `Event`.
+    ///
+    /// With `Some(timeout)` this returns `Ok(None)` when no event arrived before the timeout elapsed,
+    /// with `None` it waits until an event arrives.
+    /// The handle of this `Console` must be an input handle.
+    pub fn poll_event(&self, timeout: Option<Duration>) -> Result<Option<Event>> {
+        let millis = timeout.map_or(INFINITE, timeout_to_millis);
+
+        loop {
+            if !self.wait_for_input(millis)? {
+                return Ok(None);
+            }
+
+            if let Some(record) = self.read_single_input_event()? {
+                return Ok(Some(record.event()));
+            }
+
+            // the event was read by someone else in the meantime
+            if timeout.is_some() {
+                return Ok(None);
+            }
+        }
+    }
+
+    /// Completes a screen buffer resize event with the current size of the window.
+    ///
+    /// A `WINDOW_BUFFER_SIZE_EVENT` only carries the new size of the screen buffer, while the size of the visible window is usually what matters.
+    /// If `event` is an `Event::WindowBufferSize` this returns both sizes, the window size is queried right away, so it is as recent as possible.
+    /// Other events return `None`.
+    ///
+    /// The handle of this `Console` must be an output handle, so use a different `Console` than the one the event was read from.
+    pub fn resize_event(&self, event: &Event) -> Result<Option<ResizeEvent>> {
+        match *event {
+            Event::WindowBufferSize(buffer) => Ok(Some(ResizeEvent {
+                buffer,
+                window: self.window_size()?,
+            })),
+            _ => Ok(None),
+        }
+    }
+
+    /// Calls `callback` with the new screen buffer size every time the console is resized, from a background thread.
+    ///
+    /// The thread waits on a duplicate of the input handle of this `Console` for `WINDOW_BUFFER_SIZE_EVENT`s,
+    /// the handle must be an input handle and `ENABLE_WINDOW_INPUT` should be set in its mode to receive those events.
+    /// The thread reads every record from the input buffer, other events are discarded,
+    /// so don't read input elsewhere while the returned watcher is alive. Dropping the watcher stops the thread.
+    ///
+    /// ```no_run
+    /// # use crossterm_winapi::{Console, HandleType};
+    /// # fn main() -> std::io::Result<()> {
+    /// let console = Console::with_handle_type(HandleType::CurrentInputHandle)?;
+    ///
+    /// let _watcher = console.on_resize(|size| println!("resized to {}x{}", size.x, size.y))?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_resize(&self, callback: impl FnMut(Coord) + Send + 'static) -> Result<ResizeWatcher> {
+        self.require_input("ReadConsoleInputW")?;
+
+        ResizeWatcher::spawn(self.handle.try_clone()?, callback)
+    }
+
+    /// Returns a future that completes when the input buffer has events to read, the handle must be an input handle.
+    ///
+    /// The events are not read by the future, read them with `read_console_input` afterwards.
+    /// This is only available with the `async` feature enabled.
+    ///
+    /// ```no_run
+    /// # use crossterm_winapi::{Console, HandleType};
+    /// # async fn read() -> std::io::Result<()> {
+    /// let console = Console::with_handle_type(HandleType::CurrentInputHandle)?;
+    ///
+    /// console.readable().await?;
+    /// let (_, records) = console.read_console_input()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "async")]
+    pub fn readable(&self) -> ReadableFuture<'_> {
+        ReadableFuture::new(self)
+    }
+
+    /// Returns an iterator that blocks for the next input event and yields it.
+    ///
+    /// The records are read in batches and buffered, so there isn't a system call per event.
+    /// The iterator never returns `None`, unless the handle is closed.
+    /// The handle of this `Console` must be an input handle.
+    pub fn input_events(&self) -> ConsoleEvents<'_> {
+        ConsoleEvents {
+            console: self,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Reads all pending input records like `read_console_input`, and coalesces the resize events among them.
+    ///
+    /// Returns the screen buffer size of the last `WINDOW_BUFFER_SIZE_EVENT`, if any, and all other records in the order they were read.
+    /// This is handy while the console is resized, which reports a resize event for every step.
+    pub fn drain_coalescing_resizes(&self) -> Result<(Option<Coord>, Vec<InputRecord>)> {
+        let mut records = Vec::new();
+
+        loop {
+            let (count, read) = self.read_console_input()?;
+            if count == 0 {
+                break;
+            }
+            records.extend(read);
+        }
+
+        Ok(coalesce_resizes(records))
+    }
+
+    /// Reads all input records that are currently pending in the console input buffer.
+    ///
+    /// The number of pending events is only used as an upper bound for the read,
+    /// events could arrive or be read by someone else in the meantime, so the returned count is the number of records actually read.
+    pub fn read_console_input(&self) -> Result<(u32, Vec<InputRecord>)> {
+        loop {
+            let buf_len = self.number_of_console_input_events()?;
+
+            // Fast-skipping all the code below if there is nothing to read at all
+            if buf_len == 0 {
+                return Ok((0, vec![]));
+            }
+
+            // The buffer could be drained since the events were counted, reading would then block until new input arrives.
+            if !self.wait_for_input(0)? {
+                continue;
+            }
+
+            let mut buf: Vec<INPUT_RECORD> = Vec::with_capacity(buf_len as usize);
+            let mut size = 0;
+
+            return self.read_input(&mut buf, buf_len, &mut size);
+        }
+    }
+
+    /// Reads up to `max` input records from the console input buffer without removing them from the buffer.
+    ///
+    /// This will not block, when there is nothing to read an empty `Vec` is returned.
+    ///
+    /// Wraps the underlying function call: [PeekConsoleInputW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/peekconsoleinput]
+    pub fn peek_input(&self, max: u32) -> Result<Vec<InputRecord>> {
+        self.require_input("PeekConsoleInputW")?;
+
+        let mut buf: Vec<INPUT_RECORD> = Vec::with_capacity(max as usize);
+        let mut records_read = 0;
+
+        if !is_true(unsafe {
+            PeekConsoleInputW(*self.handle, buf.as_mut_ptr(), max, &mut records_read)
+        }) {
+            return Err(Error::last_os_error());
+        }
+
+        unsafe {
+            buf.set_len(records_read as usize);
+        }
+
+        Ok(buf.into_iter().map(InputRecord::from).collect())
+    }
+
+    /// Writes the given input records to the console input buffer, as if they were typed by the user.
+    ///
+    /// On success this returns the number of records written.
+    ///
+    /// Wraps the underlying function call: [WriteConsoleInputW]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/writeconsoleinput]
+    pub fn write_input(&self, records: &[InputRecord]) -> Result<u32> {
+        self.require_input("WriteConsoleInputW")?;
+
+        let buf: Vec<INPUT_RECORD> = records
+            .iter()
+            .map(|record| INPUT_RECORD::from(*record))
+            .collect();
+        let mut records_written = 0;
+
+        if !is_true(unsafe {
+            WriteConsoleInputW(
+                *self.handle,
+                buf.as_ptr(),
+                buf.len() as u32,
+                &mut records_written,
+            )
+        }) {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(records_written)
+    }
+
+    /// Discards all input records currently in the console input buffer.
+    ///
+    /// The handle of this `Console` must be an input handle, use `Handle::new(HandleType::InputHandle)` or `HandleType::CurrentInputHandle` to create one.
+    /// When called on an output handle this returns an `InvalidInput` error holding `ConsoleError::WrongHandleType`.
+    ///
+    /// Wraps the underlying function call: [FlushConsoleInputBuffer]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/flushconsoleinputbuffer]
+    pub fn flush_input(&self) -> Result<()> {
+        self.require_input("FlushConsoleInputBuffer")?;
+
+        if !is_true(unsafe { FlushConsoleInputBuffer(*self.handle) }) {
+            let error = Error::last_os_error();
+
+            // a console created from a raw output `HANDLE` passes `require_input`, but is refused here.
+            if error.raw_os_error() == Some(ERROR_INVALID_HANDLE as i32) {
+                return Err(ConsoleError::WrongHandleType {
+                    function: "FlushConsoleInputBuffer",
+                    handle_type: self.handle.handle_type(),
+                    needs_input: true,
+                }
+                .into());
+            }
+
+            return Err(error);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the raw input `HANDLE` of this `Console`, for example to register it with an I/O reactor.
+    ///
+    /// The handle is signaled while there are events in the input buffer,
+    /// so it can be passed to wait functions like `RegisterWaitForSingleObject`.
+    /// The `Console` must be created with an input handle type, like `HandleType::CurrentInputHandle`,
+    /// otherwise an `InvalidInput` error holding `ConsoleError::WrongHandleType` is returned.
+    /// The returned handle is only valid for as long as this `Console` is alive.
+    pub fn input_handle_raw(&self) -> Result<HANDLE> {
+        self.require_input("WaitForSingleObject")?;
+
+        Ok(*self.handle)
     }
 
+    /// Retrieves the number of unread input records in the console's input buffer.
+    ///
+    /// This only counts the records and never consumes or waits for them,
+    /// so it is safe to call from the ready callback of an I/O reactor, for example to decide whether a read would block.
+    ///
+    /// Wraps the underlying function call: [GetNumberOfConsoleInputEvents]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getnumberofconsoleinputevents]
     pub fn number_of_console_input_events(&self) -> Result<u32> {
+        self.require_input("GetNumberOfConsoleInputEvents")?;
+
         let mut buf_len: DWORD = 0;
         if !is_true(unsafe { GetNumberOfConsoleInputEvents(*self.handle, &mut buf_len) }) {
             return Err(Error::last_os_error());
@@ -203,44 +2164,1129 @@ impl Console {
         Ok(buf_len)
     }
 
+    /// Retrieves the number of buttons on the mouse used by the current console.
+    ///
+    /// Mouse input is only reported when `ENABLE_MOUSE_INPUT` is set in the console mode,
+    /// the button states of those events can be checked for this many buttons.
+    ///
+    /// Wraps the underlying function call: [GetNumberOfConsoleMouseButtons]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getnumberofconsolemousebuttons]
+    pub fn number_of_mouse_buttons(&self) -> Result<u32> {
+        let mut buttons: DWORD = 0;
+        if !is_true(unsafe { GetNumberOfConsoleMouseButtons(&mut buttons) }) {
+            return Err(Error::last_os_error());
+        }
+
+        Ok(buttons)
+    }
+
+    /// Waits until the input handle is signaled or `millis` elapsed,
+    /// returns whether input is available.
+    pub(crate) fn wait_for_input(&self, millis: DWORD) -> Result<bool> {
+        self.require_input("WaitForSingleObject")?;
+
+        match unsafe { WaitForSingleObject(*self.handle, millis) } {
+            WAIT_OBJECT_0 => Ok(true),
+            WAIT_TIMEOUT => Ok(false),
+            WAIT_FAILED => Err(Error::last_os_error()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::Other,
+                "Unexpected result while waiting for console input",
+            )),
+        }
+    }
+
+    /// Returns an `InvalidInput` error if this console is known to use an output handle.
+    fn require_input(&self, function: &'static str) -> Result<()> {
+        self.require_direction(true, function)
+    }
+
+    /// Returns an `InvalidInput` error if this console is known to use an input handle.
+    fn require_output(&self, function: &'static str) -> Result<()> {
+        self.require_direction(false, function)
+    }
+
+    /// Checks the direction of the handle before calling a function that only works for that direction,
+    /// since the error of the function call itself doesn't tell what went wrong.
+    ///
+    /// Consoles created from a raw `HANDLE` have no known handle type and are never rejected.
+    fn require_direction(&self, input: bool, function: &'static str) -> Result<()> {
+        match self.handle.handle_type() {
+            Some(handle_type) if handle_type.is_input() != input => {
+                Err(ConsoleError::WrongHandleType {
+                    function,
+                    handle_type: Some(handle_type),
+                    needs_input: input,
+                }
+                .into())
+            }
+            _ => Ok(()),
+        }
+    }
+
     fn read_input(
         &self,
         buf: &mut Vec<INPUT_RECORD>,
         buf_len: u32,
-        bytes_written: &mut u32,
+        records_read: &mut u32,
     ) -> Result<(u32, Vec<InputRecord>)> {
+        *records_read = self.read_raw_input(buf, buf_len)?;
+
+        Ok((
+            *records_read,
+            buf.iter()
+                .map(|x| InputRecord::from(*x))
+                .collect::<Vec<InputRecord>>(),
+        ))
+    }
+
+    /// Reads up to `buf_len` raw records into `buf`, replacing its contents.
+    /// The allocation of `buf` is reused, it only grows when its capacity is less than `buf_len`.
+    fn read_raw_input(&self, buf: &mut Vec<INPUT_RECORD>, buf_len: u32) -> Result<u32> {
+        self.require_input("ReadConsoleInputW")?;
+
+        buf.clear();
+        buf.reserve(buf_len as usize);
+
+        let mut records_read = 0;
         if !is_true(unsafe {
-            ReadConsoleInputW(*self.handle, buf.as_mut_ptr(), buf_len, bytes_written)
+            ReadConsoleInputW(*self.handle, buf.as_mut_ptr(), buf_len, &mut records_read)
         }) {
             return Err(Error::last_os_error());
-        } else {
-            unsafe {
-                buf.set_len(buf_len as usize);
+        }
+
+        // `ReadConsoleInputW` could read fewer records than requested,
+        // only the records it reported are initialized.
+        unsafe {
+            buf.set_len(records_read as usize);
+        }
+
+        Ok(records_read)
+    }
+}
+
+/// Encodes the given character into a single UTF-16 code unit,
+/// characters that need a surrogate pair result in a `CharSpansMultipleCells` error.
+fn encode_single_utf16(character: char) -> Result<u16> {
+    let mut utf16 = [0u16; 2];
+    let utf16 = character.encode_utf16(&mut utf16);
+
+    if utf16.len() != 1 {
+        return Err(spans_multiple_cells(character));
+    }
+
+    Ok(utf16[0])
+}
+
+/// Creates the `InvalidInput` error for a character that doesn't fit in a single cell.
+fn spans_multiple_cells(character: char) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        CharSpansMultipleCells::new(character),
+    )
+}
+
+/// Reads the pending input records into buffers that are reused between reads.
+///
+/// Unlike `Console::read_console_input` this doesn't allocate for every read,
+/// the buffers only grow when more records are pending than they can hold.
+///
+/// ```no_run
+/// # use crossterm_winapi::{Console, ConsoleReader, HandleType};
+/// # fn main() -> std::io::Result<()> {
+/// let console = Console::with_handle_type(HandleType::CurrentInputHandle)?;
+/// let mut reader = ConsoleReader::with_capacity(&console, 64);
+///
+/// loop {
+///     for record in reader.read_into()? {
+///         println!("{:?}", record);
+///     }
+/// }
+/// # }
+/// ```
+pub struct ConsoleReader<'a> {
+    console: &'a Console,
+    raw: Vec<INPUT_RECORD>,
+    records: Vec<InputRecord>,
+}
+
+impl<'a> ConsoleReader<'a> {
+    /// Create a new reader for the given console, the handle of the console must be an input handle.
+    pub fn new(console: &'a Console) -> ConsoleReader<'a> {
+        ConsoleReader::with_capacity(console, 0)
+    }
+
+    /// Create a new reader whose buffers can hold `capacity` records before they have to grow.
+    pub fn with_capacity(console: &'a Console, capacity: usize) -> ConsoleReader<'a> {
+        ConsoleReader {
+            console,
+            raw: Vec::with_capacity(capacity),
+            records: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Get the number of records the buffers can hold without growing.
+    pub fn capacity(&self) -> usize {
+        usize::min(self.raw.capacity(), self.records.capacity())
+    }
+
+    /// Reads all input records that are currently pending in the console input buffer, like `Console::read_console_input`.
+    ///
+    /// This will not block, when there is nothing to read an empty slice is returned.
+    /// The returned records are only valid until the next read.
+    pub fn read_into(&mut self) -> Result<&[InputRecord]> {
+        self.records.clear();
+
+        loop {
+            let pending = self.console.number_of_console_input_events()?;
+
+            if pending == 0 {
+                return Ok(&self.records);
+            }
+
+            // The buffer could be drained since the events were counted, reading would then block until new input arrives.
+            if !self.console.wait_for_input(0)? {
+                continue;
             }
+
+            self.console.read_raw_input(&mut self.raw, pending)?;
+            self.records
+                .extend(self.raw.iter().map(|record| InputRecord::from(*record)));
+
+            return Ok(&self.records);
         }
+    }
+}
 
-        Ok((
-            buf_len,
-            buf[..(buf_len as usize)]
-                .iter()
-                .map(|x| InputRecord::from(*x))
-                .collect::<Vec<InputRecord>>(),
-        ))
+/// An infinite stream of console input records, see `Console::input_events`.
+pub struct ConsoleEvents<'a> {
+    console: &'a Console,
+    pending: VecDeque<InputRecord>,
+}
+
+impl Iterator for ConsoleEvents<'_> {
+    type Item = Result<InputRecord>;
+
+    fn next(&mut self) -> Option<Result<InputRecord>> {
+        while self.pending.is_empty() {
+            match self.console.wait_for_input(INFINITE) {
+                Ok(_) => {}
+                Err(ref e) if e.raw_os_error() == Some(ERROR_INVALID_HANDLE as i32) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+
+            match self.console.read_console_input() {
+                Ok((_, records)) => self.pending.extend(records),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+
+        self.pending.pop_front().map(Ok)
+    }
+}
+
+/// Splits the rows of a rectangle of the given width into chunks that can be read or written in one call,
+/// returns the top row and number of rows of each chunk.
+///
+/// The buffers passed to `ReadConsoleOutputW` and `WriteConsoleOutputW` are limited in size, so large screen buffers can't be transferred at once.
+fn row_chunks(width: i16, height: i16) -> impl Iterator<Item = (i16, i16)> {
+    let rows_per_chunk = (MAX_OUTPUT_CELLS / width.max(1) as usize).max(1) as i16;
+
+    (0..height.max(0))
+        .step_by(rows_per_chunk as usize)
+        .map(move |top| (top, rows_per_chunk.min(height - top)))
+}
+
+/// Converts a timeout into milliseconds for the wait functions.
+fn timeout_to_millis(timeout: Duration) -> DWORD {
+    // `INFINITE` is `u32::MAX`, so stay below it to never wait forever.
+    timeout.as_millis().min(u128::from(INFINITE - 1)) as DWORD
+}
+
+/// Returns the number of UTF-8 bytes at the start of `string` that make up the first `utf16_len` UTF-16 code units.
+fn utf8_len_of_utf16_prefix(string: &str, utf16_len: usize) -> usize {
+    let mut utf16_count = 0;
+
+    for (index, character) in string.char_indices() {
+        if utf16_count >= utf16_len {
+            return index;
+        }
+        utf16_count += character.len_utf16();
+    }
+
+    string.len()
+}
+
+/// Returns how many cells of the given font size fit in an area of the given size in pixels.
+fn window_cells(width: i32, height: i32, font: Size) -> Result<Coord> {
+    if font.width <= 0 || font.height <= 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "The console font reports an invalid size of {}x{}",
+                font.width, font.height
+            ),
+        ));
+    }
+
+    let cells =
+        |pixels: i32, cell: i16| (pixels.max(0) / i32::from(cell)).min(i32::from(i16::MAX)) as i16;
+
+    Ok(Coord::new(
+        cells(width, font.width),
+        cells(height, font.height),
+    ))
+}
+
+/// Splits the resize events off the given records, returning the size of the last resize and the other records in order.
+fn coalesce_resizes(records: Vec<InputRecord>) -> (Option<Coord>, Vec<InputRecord>) {
+    let mut size = None;
+    let mut others = Vec::with_capacity(records.len());
+
+    for record in records {
+        match record.event() {
+            Event::WindowBufferSize(resize) => size = Some(resize),
+            _ => others.push(record),
+        }
     }
+
+    (size, others)
+}
+
+/// Returns whether `unit` is the first half of a UTF-16 surrogate pair.
+fn is_high_surrogate(unit: u16) -> bool {
+    (0xD800..0xDC00).contains(&unit)
 }
 
 impl From<Handle> for Console {
     /// Create a `Console` instance who's functions will be executed on the the given `Handle`
     fn from(handle: Handle) -> Self {
-        Console { handle }
+        Console {
+            handle,
+            supports_vt: Cell::new(None),
+            unmaximized_buffer: Cell::new(None),
+        }
     }
 }
 
 impl From<HANDLE> for Console {
     /// Create a `Console` instance who's functions will be executed on the the given `HANDLE`
     fn from(handle: HANDLE) -> Self {
-        Console {
-            handle: Handle::from(handle),
+        Console::from(Handle::from(handle))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+    use std::mem::zeroed;
+    use std::ptr;
+    use std::str;
+    use std::sync::mpsc;
+    use std::thread;
+    use std::time::Duration;
+
+    use winapi::um::wincon::{
+        FOCUS_EVENT, FOREGROUND_BLUE, FOREGROUND_GREEN, FOREGROUND_RED, INPUT_RECORD, KEY_EVENT,
+    };
+
+    use super::{
+        coalesce_resizes, encode_single_utf16, row_chunks, utf8_len_of_utf16_prefix, window_cells,
+        Console, ConsoleReader,
+    };
+    use crate::testutil::{with_input_console, with_scratch_console};
+    use crate::{
+        CharInfo, CharSpansMultipleCells, ConsoleColor, ConsoleError, ConsoleMode, Coord, Event,
+        Handle, HandleType, InputRecord, Size, WindowPositions,
+    };
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn read_console_input_while_writing() {
+        const EVENTS: u32 = 1000;
+
+        with_input_console(|console| {
+            let writer = thread::spawn(|| {
+                let console = Console::from(Handle::new(HandleType::CurrentInputHandle).unwrap());
+
+                for _ in 0..EVENTS {
+                    console.write_input(&[focus_event()]).unwrap();
+                }
+            });
+
+            let mut total = 0;
+            while total < EVENTS {
+                let (count, records) = console.read_console_input().unwrap();
+                assert_eq!(count as usize, records.len());
+                total += count;
+            }
+
+            writer.join().unwrap();
+            assert_eq!(total, EVENTS);
+        });
+    }
+
+    fn focus_event() -> InputRecord {
+        let mut raw: INPUT_RECORD = unsafe { zeroed() };
+        raw.EventType = FOCUS_EVENT;
+        InputRecord::from(raw)
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn read_input_returns_records_read() {
+        with_input_console(|console| {
+            assert!(console.peek_input(1).unwrap().is_empty());
+
+            // write a single focus event, which is ignored by the console itself.
+            assert_eq!(console.write_input(&[focus_event()]).unwrap(), 1);
+
+            // request more records than available, only the written one should be returned.
+            let mut buf = Vec::new();
+            let mut records_read = 0;
+            let (count, records) = console.read_input(&mut buf, 16, &mut records_read).unwrap();
+
+            assert_eq!(count, 1);
+            assert_eq!(records.len(), 1);
+        });
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn input_events_yields_written_records() {
+        with_input_console(|console| {
+            let records = [focus_event(), focus_event(), focus_event()];
+            assert_eq!(console.write_input(&records).unwrap(), 3);
+
+            let read: Vec<InputRecord> = console
+                .input_events()
+                .take(3)
+                .collect::<io::Result<_>>()
+                .unwrap();
+            assert_eq!(read.len(), 3);
+        });
+    }
+
+    #[cfg(feature = "async")]
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn readable_completes_when_events_are_written() {
+        use std::future::Future;
+        use std::pin::Pin;
+        use std::sync::Arc;
+        use std::task::{Context, Poll, Wake};
+
+        // wakes the test thread, which polls the future until it completes.
+        struct Unpark(thread::Thread);
+
+        impl Wake for Unpark {
+            fn wake(self: Arc<Self>) {
+                self.0.unpark();
+            }
         }
+
+        with_input_console(|console| {
+            let waker = Arc::new(Unpark(thread::current())).into();
+            let mut context = Context::from_waker(&waker);
+            let mut readable = console.readable();
+
+            assert!(Pin::new(&mut readable).poll(&mut context).is_pending());
+
+            let writer = thread::spawn(|| {
+                let console = Console::from(Handle::new(HandleType::CurrentInputHandle).unwrap());
+                console.write_input(&[focus_event()]).unwrap();
+            });
+
+            loop {
+                if let Poll::Ready(result) = Pin::new(&mut readable).poll(&mut context) {
+                    result.unwrap();
+                    break;
+                }
+                thread::park();
+            }
+
+            writer.join().unwrap();
+            assert_eq!(console.read_console_input().unwrap().0, 1);
+
+            // dropping a pending future unregisters its wait.
+            let mut pending = console.readable();
+            assert!(Pin::new(&mut pending).poll(&mut context).is_pending());
+            drop(pending);
+        });
+    }
+
+    #[test]
+    fn coalesce_resize_events() {
+        let resize = |x, y| InputRecord::from(Event::WindowBufferSize(Coord::new(x, y)));
+        let menu = InputRecord::from(Event::Menu(3));
+
+        let (size, others) =
+            coalesce_resizes(vec![resize(80, 25), focus_event(), resize(90, 30), menu]);
+        assert_eq!(size, Some(Coord::new(90, 30)));
+        assert_eq!(others, vec![focus_event(), menu]);
+
+        assert_eq!(coalesce_resizes(vec![menu]), (None, vec![menu]));
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn drain_pending_events() {
+        with_input_console(|console| {
+            let resize = InputRecord::from(Event::WindowBufferSize(Coord::new(100, 40)));
+            console
+                .write_input(&[resize, focus_event(), resize, focus_event()])
+                .unwrap();
+
+            let (size, others) = console.drain_coalescing_resizes().unwrap();
+            assert_eq!(size, Some(Coord::new(100, 40)));
+            assert_eq!(others.len(), 2);
+            assert_eq!(console.number_of_console_input_events().unwrap(), 0);
+        });
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn reader_reuses_buffers() {
+        with_input_console(|console| {
+            let mut reader = ConsoleReader::with_capacity(console, 8);
+            assert!(reader.read_into().unwrap().is_empty());
+
+            for _ in 0..3 {
+                console
+                    .write_input(&[focus_event(), focus_event()])
+                    .unwrap();
+                assert_eq!(reader.read_into().unwrap().len(), 2);
+                assert_eq!(reader.capacity(), 8);
+            }
+        });
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn key_press_and_release_are_separate_records() {
+        with_input_console(|console| {
+            let key_event = |key_down| {
+                let mut raw: INPUT_RECORD = unsafe { zeroed() };
+                raw.EventType = KEY_EVENT;
+                unsafe {
+                    let key_event = raw.Event.KeyEvent_mut();
+                    key_event.bKeyDown = key_down;
+                    key_event.wRepeatCount = 1;
+                    key_event.wVirtualKeyCode = 0x41;
+                    *key_event.uChar.UnicodeChar_mut() = u16::from(b'a');
+                }
+                InputRecord::from(raw)
+            };
+
+            console.write_input(&[key_event(1), key_event(0)]).unwrap();
+
+            let (count, records) = console.read_console_input().unwrap();
+            assert_eq!(count, 2);
+
+            let pressed = records[0].as_key().unwrap();
+            let released = records[1].as_key().unwrap();
+            assert!(pressed.is_pressed() && !pressed.is_released());
+            assert!(released.is_released() && !released.is_pressed());
+            assert_eq!(pressed.virtual_key_code, released.virtual_key_code);
+        });
+    }
+
+    #[test]
+    #[ignore = "changes the mode of the real console"]
+    fn mouse_capture_disables_quick_edit() {
+        let handle = Handle::new(HandleType::CurrentInputHandle).unwrap();
+        let original_mode = ConsoleMode::from(handle.try_clone().unwrap())
+            .mode()
+            .unwrap();
+
+        // the guard restores the mode when it is dropped, also when one of the asserts below panics.
+        let console = Console::from(handle.try_clone().unwrap());
+        let guard = console.enable_mouse_capture().unwrap();
+        let mode = console.mode().unwrap();
+        assert_ne!(mode & ConsoleMode::ENABLE_MOUSE_INPUT, 0);
+        assert_ne!(mode & ConsoleMode::ENABLE_EXTENDED_FLAGS, 0);
+        assert_eq!(mode & ConsoleMode::ENABLE_QUICK_EDIT_MODE, 0);
+
+        guard.disable().unwrap();
+        assert_eq!(ConsoleMode::from(handle).mode().unwrap(), original_mode);
+
+        let output = Console::new().unwrap();
+        assert!(output.enable_mouse_capture().is_err());
+    }
+
+    #[test]
+    fn console_from_handle_ref() {
+        let handle = Handle::new(HandleType::CurrentOutputHandle).unwrap();
+        let console = Console::from_ref(&handle).unwrap();
+
+        assert!(console.screen_buffer_info().is_ok());
+        drop(console);
+        assert!(handle.is_console());
+    }
+
+    #[test]
+    fn set_text_attribute_from_rgb() {
+        with_scratch_console(|console| {
+            console
+                .set_text_attribute_rgb((250, 240, 30), (0, 0, 120))
+                .unwrap();
+            assert_eq!(console.current_attributes().unwrap(), 0x1E);
+        });
+    }
+
+    #[test]
+    fn set_foreground_and_background() {
+        with_scratch_console(|console| {
+            // 0x8000 is `COMMON_LVB_UNDERSCORE`, which should be kept.
+            console.set_text_attribute(0x8000 | 0x1E).unwrap();
+
+            console.set_foreground(ConsoleColor::Red).unwrap();
+            assert_eq!(console.current_attributes().unwrap(), 0x8000 | 0x1C);
+
+            console.set_background(ConsoleColor::DarkGrey).unwrap();
+            assert_eq!(console.current_attributes().unwrap(), 0x8000 | 0x8C);
+        });
+    }
+
+    #[test]
+    fn reset_to_default_attributes() {
+        with_scratch_console(|console| {
+            console.set_text_attribute(0x8000 | 0x1E).unwrap();
+            console.reset_attributes().unwrap();
+
+            assert_eq!(
+                console.current_attributes().unwrap(),
+                FOREGROUND_RED | FOREGROUND_GREEN | FOREGROUND_BLUE
+            );
+        });
+    }
+
+    #[test]
+    fn set_and_restore_color_table() {
+        with_scratch_console(|console| {
+            let original = console.color_table().unwrap();
+
+            let mut table = original;
+            table[1] = (1, 2, 3);
+            console.set_color_table(&table).unwrap();
+            assert_eq!(console.color_table().unwrap(), table);
+
+            console.set_color_table(&original).unwrap();
+            assert_eq!(console.color_table().unwrap(), original);
+        });
+    }
+
+    #[test]
+    #[ignore = "writes into the input buffer of the console running the tests"]
+    fn resize_watcher_reports_resize_events() {
+        with_input_console(|console| {
+            let (sender, receiver) = mpsc::channel();
+            let watcher = console
+                .on_resize(move |size| sender.send(size).unwrap())
+                .unwrap();
+
+            let resize = InputRecord::from(Event::WindowBufferSize(Coord::new(100, 40)));
+            console.write_input(&[focus_event(), resize]).unwrap();
+
+            assert_eq!(
+                receiver.recv_timeout(Duration::from_secs(5)).unwrap(),
+                Coord::new(100, 40)
+            );
+
+            // the thread is stopped when the watcher is dropped, which drops the sender.
+            drop(watcher);
+            assert!(receiver.recv().is_err());
+        });
+    }
+
+    #[test]
+    fn window_rect_round_trip() {
+        with_scratch_console(|console| {
+            let rect = console.window_rect().unwrap();
+
+            console.set_console_info(true, rect).unwrap();
+            assert_eq!(console.window_rect().unwrap(), rect);
+            assert_eq!(
+                console.window_size().unwrap(),
+                Coord::new(rect.width(), rect.height())
+            );
+        });
+    }
+
+    #[test]
+    fn relative_window_rect() {
+        with_scratch_console(|console| {
+            let size = console.window_size().unwrap();
+
+            // move the right and bottom side one cell back, which shrinks the window by a column and a row.
+            let shrink = WindowPositions {
+                left: 0,
+                right: -1,
+                bottom: -1,
+                top: 0,
+            };
+            console.set_console_info(false, shrink).unwrap();
+            assert_eq!(
+                console.window_size().unwrap(),
+                Coord::new(size.x - 1, size.y - 1)
+            );
+
+            // the same rectangle is rejected as an absolute window.
+            assert!(console.set_console_info(true, shrink).is_err());
+        });
+    }
+
+    #[test]
+    fn maximize_and_restore_window() {
+        with_scratch_console(|console| {
+            let original = console.window_size().unwrap();
+            let original_buffer = console.buffer_size().unwrap();
+
+            console.maximize().unwrap();
+            let largest = console.largest_window_size();
+            assert_eq!(console.window_size().unwrap(), largest);
+            let buffer = console.buffer_size().unwrap();
+            assert!(buffer.x >= largest.x && buffer.y >= largest.y);
+
+            console.restore(original).unwrap();
+            assert_eq!(console.window_size().unwrap(), original);
+            assert_eq!(console.buffer_size().unwrap(), original_buffer);
+            assert!(console.restore(Coord::new(0, 10)).is_err());
+
+            // a window that doesn't fit on the screen is rejected before the buffer is grown.
+            let error = console
+                .restore(Coord::new(largest.x + 1, largest.y))
+                .unwrap_err();
+            assert!(matches!(
+                ConsoleError::from(error),
+                ConsoleError::WindowTooLarge { .. }
+            ));
+            assert_eq!(console.buffer_size().unwrap(), original_buffer);
+        });
+    }
+
+    #[test]
+    fn scroll_window_contents() {
+        with_scratch_console(|console| {
+            let window = console.screen_buffer_info().unwrap().terminal_window();
+            let top = Coord::new(window.left, window.top);
+            let below_top = Coord::new(window.left, window.top + 1);
+
+            console.write_string_at("a", below_top).unwrap();
+
+            console.scroll_up(1).unwrap();
+            assert_eq!(console.read_string_at(top, 1).unwrap(), "a");
+            assert_eq!(console.read_string_at(below_top, 1).unwrap(), " ");
+
+            console.scroll_down(1).unwrap();
+            assert_eq!(console.read_string_at(top, 1).unwrap(), " ");
+            assert_eq!(console.read_string_at(below_top, 1).unwrap(), "a");
+
+            console.scroll_up(u16::MAX).unwrap();
+            assert_eq!(console.read_string_at(below_top, 1).unwrap(), " ");
+        });
+    }
+
+    #[test]
+    fn reject_wrong_handle_direction() {
+        let input = Console::with_handle_type(HandleType::CurrentInputHandle).unwrap();
+        let error = input.set_text_attribute(0x0F).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(matches!(
+            ConsoleError::from(error),
+            ConsoleError::WrongHandleType {
+                function: "SetConsoleTextAttribute",
+                handle_type: Some(HandleType::CurrentInputHandle),
+                needs_input: false,
+            }
+        ));
+
+        let output = Console::with_handle_type(HandleType::CurrentOutputHandle).unwrap();
+        let error = output.peek_input(1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(output.input_handle_raw().is_err());
+        assert!(input.input_handle_raw().is_ok());
+    }
+
+    #[test]
+    fn write_translated_line_breaks() {
+        with_scratch_console(|console| {
+            // without processed output a line feed doesn't return to the first column.
+            console.set_mode(0).unwrap();
+            console.set_cursor_position(Coord::new(3, 0)).unwrap();
+
+            assert_eq!(console.write_str_translated("ab\ncd\r\ne").unwrap(), 8);
+
+            assert_eq!(console.read_string_at(Coord::new(3, 0), 2).unwrap(), "ab");
+            assert_eq!(console.read_string_at(Coord::new(0, 1), 2).unwrap(), "cd");
+            assert_eq!(console.read_string_at(Coord::new(0, 2), 1).unwrap(), "e");
+            assert_eq!(console.cursor_position().unwrap(), Coord::new(1, 2));
+        });
+    }
+
+    #[test]
+    fn write_translated_trailing_carriage_return() {
+        with_scratch_console(|console| {
+            // processed output is on for a new screen buffer, so a written `\r` returns to the first column.
+            console.set_cursor_position(Coord::new(2, 0)).unwrap();
+
+            assert_eq!(console.write_str_translated("abc\r").unwrap(), 4);
+            assert_eq!(console.read_string_at(Coord::new(2, 0), 3).unwrap(), "abc");
+            assert_eq!(console.cursor_position().unwrap(), Coord::ORIGIN);
+        });
+    }
+
+    #[test]
+    fn write_translated_on_last_row() {
+        with_scratch_console(|console| {
+            let last_row = console.buffer_size().unwrap().y - 1;
+            console
+                .set_cursor_position(Coord::new(0, last_row))
+                .unwrap();
+
+            console.write_str_translated("x\ny").unwrap();
+
+            assert_eq!(
+                console
+                    .read_string_at(Coord::new(0, last_row - 1), 1)
+                    .unwrap(),
+                "x"
+            );
+            assert_eq!(
+                console.read_string_at(Coord::new(0, last_row), 1).unwrap(),
+                "y"
+            );
+        });
+    }
+
+    #[test]
+    fn write_string_at_position() {
+        with_scratch_console(|console| {
+            let cursor = console.cursor_position().unwrap();
+
+            assert_eq!(
+                console.write_string_at("hello", Coord::new(2, 1)).unwrap(),
+                5
+            );
+            assert_eq!(console.cursor_position().unwrap(), cursor);
+
+            let (cells, _) = console
+                .read_output(
+                    Coord::new(5, 1),
+                    Coord::ORIGIN,
+                    WindowPositions {
+                        left: 2,
+                        right: 6,
+                        bottom: 1,
+                        top: 1,
+                    },
+                )
+                .unwrap();
+
+            let text: String = cells.iter().map(|cell| cell.ch).collect();
+            assert_eq!(text, "hello");
+        });
+    }
+
+    #[test]
+    fn write_string_at_wraps_to_next_row() {
+        with_scratch_console(|console| {
+            let width = console.buffer_size().unwrap().x;
+
+            console
+                .write_string_at("abcd", Coord::new(width - 2, 0))
+                .unwrap();
+
+            assert_eq!(
+                console.read_string_at(Coord::new(width - 2, 0), 2).unwrap(),
+                "ab"
+            );
+            assert_eq!(console.read_string_at(Coord::new(0, 1), 2).unwrap(), "cd");
+        });
+    }
+
+    #[test]
+    fn write_string_at_clamps_position() {
+        with_scratch_console(|console| {
+            console.write_string_at("x", Coord::new(-4, -1)).unwrap();
+            assert_eq!(console.read_string_at(Coord::ORIGIN, 1).unwrap(), "x");
+        });
+    }
+
+    #[test]
+    fn fill_with_ascii_character() {
+        with_scratch_console(|console| {
+            assert_eq!(
+                console
+                    .fill_with_character(Coord::new(1, 0), 3, '*')
+                    .unwrap(),
+                3
+            );
+
+            assert_eq!(console.read_string_at(Coord::ORIGIN, 5).unwrap(), " *** ");
+        });
+    }
+
+    #[test]
+    fn fill_with_wide_character_is_rejected() {
+        with_scratch_console(|console| {
+            let error = console
+                .fill_with_character_wide(Coord::ORIGIN, 4, '漢')
+                .unwrap_err();
+
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            assert!(error.to_string().contains("two cells wide"));
+        });
+    }
+
+    #[test]
+    fn fill_beyond_buffer_is_rejected() {
+        with_scratch_console(|console| {
+            let size = console.buffer_size().unwrap();
+            let cells = size.x as u32 * size.y as u32;
+            let last_row = Coord::new(0, size.y - 1);
+
+            assert_eq!(
+                console
+                    .fill_with_character(last_row, size.x as u32, '-')
+                    .unwrap(),
+                size.x as u32
+            );
+
+            let error = console
+                .fill_with_character(last_row, size.x as u32 + 1, '-')
+                .unwrap_err();
+            assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+            assert!(matches!(
+                ConsoleError::from(error),
+                ConsoleError::FillOutOfRange { count, .. } if count == size.x as u32 + 1
+            ));
+            assert!(console
+                .fill_with_character(Coord::ORIGIN, cells, ' ')
+                .is_ok());
+            assert!(matches!(
+                ConsoleError::from(
+                    console
+                        .fill_with_character(Coord::new(-1, 0), 1, ' ')
+                        .unwrap_err()
+                ),
+                ConsoleError::InvalidCoord(_)
+            ));
+
+            // the wide character and attribute fills are checked the same way.
+            assert!(console
+                .fill_with_character_wide(last_row, size.x as u32 + 1, '-')
+                .is_err());
+            assert!(console
+                .fill_with_attribute(last_row, size.x as u32 + 1, 0x07)
+                .is_err());
+            assert!(console
+                .fill_with_attribute(Coord::ORIGIN, cells, 0x07)
+                .is_ok());
+        });
+    }
+
+    #[test]
+    fn fill_with_wide_character() {
+        with_scratch_console(|console| {
+            let width = console.buffer_size().unwrap().x;
+            let count = width as u32 + 2;
+
+            // fills continue on the next row.
+            assert_eq!(
+                console
+                    .fill_with_character_wide(Coord::ORIGIN, count, '─')
+                    .unwrap(),
+                count
+            );
+
+            let expected = "─".repeat(count as usize);
+            assert_eq!(
+                console.read_string_at(Coord::ORIGIN, count).unwrap(),
+                expected
+            );
+        });
+    }
+
+    #[test]
+    fn write_styled_at_position() {
+        with_scratch_console(|console| {
+            let width = console.buffer_size().unwrap().x;
+            let end_of_row = Coord::new(width - 2, 0);
+            let untouched = console.read_attributes_at(Coord::new(1, 1), 1).unwrap()[0];
+
+            // the text continues on the next row, only the cells it was written to get the attributes.
+            assert_eq!(console.write_styled_at("abc", end_of_row, 0x1F).unwrap(), 3);
+            assert_eq!(console.read_string_at(end_of_row, 3).unwrap(), "abc");
+            assert_eq!(
+                console.read_attributes_at(end_of_row, 4).unwrap(),
+                vec![0x1F, 0x1F, 0x1F, untouched]
+            );
+        });
+    }
+
+    #[test]
+    fn write_attributes_at_position() {
+        with_scratch_console(|console| {
+            console.write_string_at("abc", Coord::ORIGIN).unwrap();
+            assert_eq!(
+                console
+                    .write_attributes_at(&[0x1F, 0x2E], Coord::new(1, 0))
+                    .unwrap(),
+                2
+            );
+
+            let (cells, _) = console
+                .read_output(
+                    Coord::new(3, 1),
+                    Coord::ORIGIN,
+                    WindowPositions {
+                        left: 0,
+                        right: 2,
+                        bottom: 0,
+                        top: 0,
+                    },
+                )
+                .unwrap();
+
+            let text: String = cells.iter().map(|cell| cell.ch).collect();
+            assert_eq!(text, "abc");
+            assert_eq!(cells[1].attributes, 0x1F);
+            assert_eq!(cells[2].attributes, 0x2E);
+        });
+    }
+
+    #[test]
+    fn read_cells_at_position() {
+        with_scratch_console(|console| {
+            console.write_string_at("xyz", Coord::new(4, 2)).unwrap();
+            console
+                .write_attributes_at(&[0x0A, 0x0B, 0x0C], Coord::new(4, 2))
+                .unwrap();
+
+            assert_eq!(console.read_string_at(Coord::new(4, 2), 3).unwrap(), "xyz");
+            assert_eq!(
+                console.read_attributes_at(Coord::new(4, 2), 3).unwrap(),
+                vec![0x0A, 0x0B, 0x0C]
+            );
+        });
+    }
+
+    #[test]
+    fn surrogate_pairs_in_cells() {
+        with_scratch_console(|console| {
+            // a surrogate pair takes two cells and is read back from both of them.
+            assert_eq!(console.write_string_at("😀", Coord::ORIGIN).unwrap(), 2);
+            assert_eq!(console.read_string_at(Coord::ORIGIN, 2).unwrap(), "😀");
+
+            let fill = console.fill_with_character_wide(Coord::new(0, 1), 1, '😀');
+            assert_eq!(fill.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+            let write = console.write_output(
+                &[CharInfo::new('😀', 0x07)],
+                Coord::new(1, 1),
+                Coord::ORIGIN,
+                WindowPositions {
+                    left: 0,
+                    right: 0,
+                    bottom: 1,
+                    top: 1,
+                },
+            );
+            assert_eq!(write.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        });
+    }
+
+    #[test]
+    fn write_invalid_utf8() {
+        // the input is validated before the handle is used, so no console is needed.
+        let console = Console::from(ptr::null_mut());
+
+        let error = console.write_char_buffer(&[b'a', 0xff]).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(error.into_inner().unwrap().is::<str::Utf8Error>());
+    }
+
+    #[test]
+    fn save_and_restore_screen() {
+        with_scratch_console(|console| {
+            console.write_str("before").unwrap();
+            let snapshot = console.save_screen().unwrap();
+
+            console.clear().unwrap();
+            console.write_str("overlay").unwrap();
+            console.restore_screen(&snapshot).unwrap();
+
+            assert_eq!(console.save_screen().unwrap(), snapshot);
+            assert_eq!(console.cursor_position().unwrap(), Coord::new(6, 0));
+        });
+    }
+
+    #[test]
+    fn cells_that_fit_in_window() {
+        assert_eq!(
+            window_cells(1000, 500, Size::new(8, 16)).unwrap(),
+            Coord::new(125, 31)
+        );
+        assert_eq!(
+            window_cells(-10, 20, Size::new(8, 16)).unwrap(),
+            Coord::new(0, 1)
+        );
+        assert!(window_cells(1000, 500, Size::new(0, 16)).is_err());
+    }
+
+    #[test]
+    fn row_chunks_cover_all_rows() {
+        let chunks: Vec<(i16, i16)> = row_chunks(4096, 5).collect();
+        assert_eq!(chunks, vec![(0, 2), (2, 2), (4, 1)]);
+        assert_eq!(row_chunks(80, 0).count(), 0);
+    }
+
+    #[test]
+    fn write_large_buffer() {
+        with_scratch_console(|console| {
+            let text = "abcdefghijklmnopqrstuvwxyz😀\n".repeat(128 * 1024);
+
+            let written = console.write_char_buffer_checked(text.as_bytes()).unwrap();
+
+            assert_eq!(written.bytes_consumed, text.len());
+            assert_eq!(written.cells_written as usize, text.encode_utf16().count());
+        });
+    }
+
+    #[test]
+    fn write_utf16_buffer() {
+        with_scratch_console(|console| {
+            let text: Vec<u16> = "a😀b".encode_utf16().collect();
+
+            assert_eq!(console.write_utf16(&text).unwrap(), 4);
+            assert_eq!(console.write_utf16(&[]).unwrap(), 0);
+        });
+    }
+
+    #[test]
+    fn encode_single_utf16_unit() {
+        assert_eq!(encode_single_utf16('a').unwrap(), 0x61);
+        assert_eq!(encode_single_utf16('─').unwrap(), 0x2500);
+
+        let error = encode_single_utf16('😀').unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error
+                .get_ref()
+                .and_then(|e| e.downcast_ref::<CharSpansMultipleCells>())
+                .map(CharSpansMultipleCells::character),
+            Some('😀')
+        );
+    }
+
+    #[test]
+    fn utf8_len_of_partial_write() {
+        assert_eq!(utf8_len_of_utf16_prefix("abc", 0), 0);
+        assert_eq!(utf8_len_of_utf16_prefix("abc", 2), 2);
+        assert_eq!(utf8_len_of_utf16_prefix("abc", 3), 3);
+        assert_eq!(utf8_len_of_utf16_prefix("aé€b", 3), 6);
+        assert_eq!(utf8_len_of_utf16_prefix("a😀b", 3), 5);
     }
 }
@@ -0,0 +1,225 @@
+//! This module provides the 16 standard colors of the console and helpers to build character attributes from them.
+
+/// One of the 16 standard console colors.
+///
+/// The discriminant is the 4 bit color value, which is made up of the blue, green, red and intensity bits.
+/// This is the same value as used in the foreground part of a character attribute.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ConsoleColor {
+    Black = 0x0,
+    DarkBlue = 0x1,
+    DarkGreen = 0x2,
+    DarkCyan = 0x3,
+    DarkRed = 0x4,
+    DarkMagenta = 0x5,
+    DarkYellow = 0x6,
+    Grey = 0x7,
+    DarkGrey = 0x8,
+    Blue = 0x9,
+    Green = 0xA,
+    Cyan = 0xB,
+    Red = 0xC,
+    Magenta = 0xD,
+    Yellow = 0xE,
+    White = 0xF,
+}
+
+impl ConsoleColor {
+    const ALL: [ConsoleColor; 16] = [
+        ConsoleColor::Black,
+        ConsoleColor::DarkBlue,
+        ConsoleColor::DarkGreen,
+        ConsoleColor::DarkCyan,
+        ConsoleColor::DarkRed,
+        ConsoleColor::DarkMagenta,
+        ConsoleColor::DarkYellow,
+        ConsoleColor::Grey,
+        ConsoleColor::DarkGrey,
+        ConsoleColor::Blue,
+        ConsoleColor::Green,
+        ConsoleColor::Cyan,
+        ConsoleColor::Red,
+        ConsoleColor::Magenta,
+        ConsoleColor::Yellow,
+        ConsoleColor::White,
+    ];
+
+    /// The RGB values of the colors in the default console palette, indexed by color value.
+    const PALETTE: [(u8, u8, u8); 16] = [
+        (0, 0, 0),
+        (0, 0, 128),
+        (0, 128, 0),
+        (0, 128, 128),
+        (128, 0, 0),
+        (128, 0, 128),
+        (128, 128, 0),
+        (192, 192, 192),
+        (128, 128, 128),
+        (0, 0, 255),
+        (0, 255, 0),
+        (0, 255, 255),
+        (255, 0, 0),
+        (255, 0, 255),
+        (255, 255, 0),
+        (255, 255, 255),
+    ];
+
+    /// Get the console color that is nearest to the given RGB color in the default console palette.
+    ///
+    /// The distance is the Euclidean distance with the squared differences of red, green and blue weighted 2, 4 and 3,
+    /// which approximates how sensitive the eye is to each of them.
+    pub fn from_rgb(r: u8, g: u8, b: u8) -> ConsoleColor {
+        ConsoleColor::from_rgb_in_palette(r, g, b, &ConsoleColor::PALETTE)
+    }
+
+    /// Get the console color that is nearest to the given RGB color in the given palette, indexed by color value.
+    ///
+    /// Pass the palette of `Console::color_table` to quantize for the colors the console actually shows,
+    /// the distance is the same as for `from_rgb`.
+    pub fn from_rgb_in_palette(r: u8, g: u8, b: u8, palette: &[(u8, u8, u8); 16]) -> ConsoleColor {
+        let distance = |&(pr, pg, pb): &(u8, u8, u8)| {
+            let dr = i32::from(r) - i32::from(pr);
+            let dg = i32::from(g) - i32::from(pg);
+            let db = i32::from(b) - i32::from(pb);
+            2 * dr * dr + 4 * dg * dg + 3 * db * db
+        };
+
+        let index = (0..16).min_by_key(|&i| distance(&palette[i])).unwrap_or(0);
+        ConsoleColor::ALL[index]
+    }
+
+    /// Get the console color that is nearest to the given color of the 256 color ANSI palette.
+    ///
+    /// The first 16 values are the standard and bright ANSI colors, the other ones are quantized with `from_rgb`.
+    pub fn from_ansi_value(value: u8) -> ConsoleColor {
+        const ANSI_ORDER: [ConsoleColor; 16] = [
+            ConsoleColor::Black,
+            ConsoleColor::DarkRed,
+            ConsoleColor::DarkGreen,
+            ConsoleColor::DarkYellow,
+            ConsoleColor::DarkBlue,
+            ConsoleColor::DarkMagenta,
+            ConsoleColor::DarkCyan,
+            ConsoleColor::Grey,
+            ConsoleColor::DarkGrey,
+            ConsoleColor::Red,
+            ConsoleColor::Green,
+            ConsoleColor::Yellow,
+            ConsoleColor::Blue,
+            ConsoleColor::Magenta,
+            ConsoleColor::Cyan,
+            ConsoleColor::White,
+        ];
+
+        match value {
+            0..=15 => ANSI_ORDER[value as usize],
+            16..=231 => {
+                // 6x6x6 color cube
+                let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+                let index = value - 16;
+                ConsoleColor::from_rgb(level(index / 36), level(index / 6 % 6), level(index % 6))
+            }
+            _ => {
+                // grayscale ramp
+                let gray = 8 + (value - 232) * 10;
+                ConsoleColor::from_rgb(gray, gray, gray)
+            }
+        }
+    }
+
+    /// Get the foreground color of the given character attributes.
+    pub fn from_foreground_bits(attr: u16) -> ConsoleColor {
+        ConsoleColor::ALL[(attr & 0x000F) as usize]
+    }
+
+    /// Get the background color of the given character attributes.
+    pub fn from_background_bits(attr: u16) -> ConsoleColor {
+        ConsoleColor::ALL[((attr & 0x00F0) >> 4) as usize]
+    }
+
+    /// Get the attribute bits that set this color as the foreground color.
+    pub fn foreground_bits(self) -> u16 {
+        self as u16
+    }
+
+    /// Get the attribute bits that set this color as the background color.
+    pub fn background_bits(self) -> u16 {
+        (self as u16) << 4
+    }
+}
+
+/// Build the character attributes for the given foreground and background color,
+/// which can be passed to functions like `Console::set_text_attribute`.
+pub fn attributes(fg: ConsoleColor, bg: ConsoleColor) -> u16 {
+    fg.foreground_bits() | bg.background_bits()
+}
+
+#[cfg(test)]
+mod tests {
+    use winapi::um::wincon::{
+        BACKGROUND_BLUE, BACKGROUND_INTENSITY, FOREGROUND_GREEN, FOREGROUND_INTENSITY,
+        FOREGROUND_RED,
+    };
+
+    use super::{attributes, ConsoleColor};
+
+    #[test]
+    fn build_attributes() {
+        assert_eq!(
+            attributes(ConsoleColor::Yellow, ConsoleColor::Blue),
+            FOREGROUND_RED
+                | FOREGROUND_GREEN
+                | FOREGROUND_INTENSITY
+                | BACKGROUND_BLUE
+                | BACKGROUND_INTENSITY
+        );
+        assert_eq!(attributes(ConsoleColor::Black, ConsoleColor::Black), 0);
+    }
+
+    #[test]
+    fn quantize_rgb() {
+        assert_eq!(ConsoleColor::from_rgb(0, 0, 0), ConsoleColor::Black);
+        assert_eq!(ConsoleColor::from_rgb(250, 10, 10), ConsoleColor::Red);
+        assert_eq!(
+            ConsoleColor::from_rgb(120, 0, 140),
+            ConsoleColor::DarkMagenta
+        );
+        assert_eq!(ConsoleColor::from_rgb(200, 200, 200), ConsoleColor::Grey);
+    }
+
+    #[test]
+    fn quantize_rgb_in_palette() {
+        // a palette where every color is black, except for a dark orange in the slot of dark yellow.
+        let mut palette = [(0, 0, 0); 16];
+        palette[ConsoleColor::DarkYellow as usize] = (200, 100, 0);
+
+        assert_eq!(
+            ConsoleColor::from_rgb_in_palette(210, 110, 10, &palette),
+            ConsoleColor::DarkYellow
+        );
+        assert_eq!(
+            ConsoleColor::from_rgb_in_palette(5, 5, 5, &palette),
+            ConsoleColor::Black
+        );
+    }
+
+    #[test]
+    fn quantize_ansi_value() {
+        assert_eq!(ConsoleColor::from_ansi_value(1), ConsoleColor::DarkRed);
+        assert_eq!(ConsoleColor::from_ansi_value(14), ConsoleColor::Cyan);
+        assert_eq!(ConsoleColor::from_ansi_value(196), ConsoleColor::Red);
+        assert_eq!(ConsoleColor::from_ansi_value(231), ConsoleColor::White);
+        assert_eq!(ConsoleColor::from_ansi_value(232), ConsoleColor::Black);
+    }
+
+    #[test]
+    fn decode_attributes() {
+        let attr = attributes(ConsoleColor::DarkCyan, ConsoleColor::Red) | 0x8000;
+
+        assert_eq!(
+            ConsoleColor::from_foreground_bits(attr),
+            ConsoleColor::DarkCyan
+        );
+        assert_eq!(ConsoleColor::from_background_bits(attr), ConsoleColor::Red);
+    }
+}
@@ -1,6 +1,7 @@
 use std::io::{Error, Result};
 
 use winapi::um::consoleapi::{GetConsoleMode, SetConsoleMode};
+use winapi::um::wincon;
 use winapi::um::winnt::HANDLE;
 
 use super::{is_true, Handle, HandleType};
@@ -18,6 +19,34 @@ pub struct ConsoleMode {
 }
 
 impl ConsoleMode {
+    /// Input mode: CTRL+C is processed by the system and is not placed in the input buffer.
+    pub const ENABLE_PROCESSED_INPUT: u32 = wincon::ENABLE_PROCESSED_INPUT;
+    /// Input mode: reading returns only when a carriage return is read.
+    pub const ENABLE_LINE_INPUT: u32 = wincon::ENABLE_LINE_INPUT;
+    /// Input mode: characters read are written to the active screen buffer, requires `ENABLE_LINE_INPUT`.
+    pub const ENABLE_ECHO_INPUT: u32 = wincon::ENABLE_ECHO_INPUT;
+    /// Input mode: screen buffer size changes are reported in the input buffer.
+    pub const ENABLE_WINDOW_INPUT: u32 = wincon::ENABLE_WINDOW_INPUT;
+    /// Input mode: mouse events are reported in the input buffer.
+    pub const ENABLE_MOUSE_INPUT: u32 = wincon::ENABLE_MOUSE_INPUT;
+    /// Input mode: text entered is inserted at the cursor instead of overwriting.
+    pub const ENABLE_INSERT_MODE: u32 = wincon::ENABLE_INSERT_MODE;
+    /// Input mode: the user can select and edit text with the mouse, requires `ENABLE_EXTENDED_FLAGS`.
+    pub const ENABLE_QUICK_EDIT_MODE: u32 = wincon::ENABLE_QUICK_EDIT_MODE;
+    /// Input mode: required to enable or disable `ENABLE_QUICK_EDIT_MODE` and `ENABLE_INSERT_MODE`.
+    pub const ENABLE_EXTENDED_FLAGS: u32 = wincon::ENABLE_EXTENDED_FLAGS;
+    /// Input mode: user input is converted into virtual terminal sequences.
+    pub const ENABLE_VIRTUAL_TERMINAL_INPUT: u32 = wincon::ENABLE_VIRTUAL_TERMINAL_INPUT;
+
+    /// Output mode: control sequences like backspace, tab and line feed are processed.
+    pub const ENABLE_PROCESSED_OUTPUT: u32 = wincon::ENABLE_PROCESSED_OUTPUT;
+    /// Output mode: the cursor moves to the next line when reaching the end of a line.
+    pub const ENABLE_WRAP_AT_EOL_OUTPUT: u32 = wincon::ENABLE_WRAP_AT_EOL_OUTPUT;
+    /// Output mode: virtual terminal (ANSI) escape sequences are processed.
+    pub const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = wincon::ENABLE_VIRTUAL_TERMINAL_PROCESSING;
+    /// Output mode: a line feed does not return the cursor to the start of the line.
+    pub const DISABLE_NEWLINE_AUTO_RETURN: u32 = wincon::DISABLE_NEWLINE_AUTO_RETURN;
+
     /// Create a new `ConsoleMode` instance.
     ///
     /// This will use the `STD_OUTPUT_HANDLE` as default handle.
@@ -53,7 +82,6 @@ impl ConsoleMode {
         let mut console_mode = 0;
         unsafe {
             if !is_true(GetConsoleMode(*self.handle, &mut console_mode)) {
-                println!("Getting mode failed");
                 return Err(Error::last_os_error());
             }
         }
@@ -46,6 +46,13 @@ impl ScreenBufferInfo {
         self.0.wAttributes
     }
 
+    /// This will return the maximum size the window could have, given the buffer size, font size and screen size.
+    ///
+    /// Will take `dwMaximumWindowSize` from the current screen buffer and convert it into the `Size`.
+    pub fn max_window_size(&self) -> Size {
+        Size::from(self.0.dwMaximumWindowSize)
+    }
+
     /// This will return the current cursor position.
     ///
     /// Will take `dwCursorPosition` from the current screen buffer.
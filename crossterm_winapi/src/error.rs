@@ -0,0 +1,317 @@
+//! This module contains the error type that tells the different failures of the console functions apart.
+//!
+//! The console functions keep returning `io::Result` rather than `Result<T, ConsoleError>`, so existing code that uses them
+//! with `?` in functions returning `io::Result`, like the rest of crossterm, keeps compiling.
+//! The `ConsoleError` is stored inside the returned `io::Error` instead, `ConsoleError::from` gets it back out.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::str::Utf8Error;
+
+use super::{CharSpansMultipleCells, Coord, HandleType, WindowPositions};
+
+/// The reason a console function failed.
+///
+/// The functions of this crate return an `io::Error`, so they can be used with `?` in any function that returns `io::Result`.
+/// Every argument the console functions reject themselves is reported as one of the variants below, stored inside that `io::Error`,
+/// so converting it into a `ConsoleError` recovers them. Only the failures of the WinApi calls themselves become `ConsoleError::Io`:
+///
+/// ```no_run
+/// # use crossterm_winapi::{Console, ConsoleError, Coord};
+/// let console = Console::new().unwrap();
+///
+/// match console.set_cursor_position(Coord::new(-1, 0)).map_err(ConsoleError::from) {
+///     Err(ConsoleError::InvalidCoord(coord)) => println!("{:?} is outside of the buffer", coord),
+///     Err(e) => println!("failed: {}", e),
+///     Ok(()) => {}
+/// }
+/// ```
+#[derive(Debug)]
+pub enum ConsoleError {
+    /// The operating system refused the function call, or another I/O error occurred.
+    Io(io::Error),
+    /// The coordinate lies outside of the screen buffer.
+    InvalidCoord(Coord),
+    /// The function needs a handle of the other direction, like an input handle for reading input.
+    WrongHandleType {
+        /// the WinApi function that was about to be called.
+        function: &'static str,
+        /// the type of the handle the console was created with, `None` for a console created from a raw `HANDLE`.
+        handle_type: Option<HandleType>,
+        /// whether the function needs an input handle, otherwise it needs an output handle.
+        needs_input: bool,
+    },
+    /// The text to write is not valid UTF-8.
+    Utf8(Utf8Error),
+    /// The character has to fit in a single cell, but doesn't.
+    CharSpansMultipleCells(CharSpansMultipleCells),
+    /// Filling `count` cells from `start` reaches beyond the last cell of the screen buffer.
+    FillOutOfRange {
+        /// the first cell to fill.
+        start: Coord,
+        /// the number of cells to fill.
+        count: u32,
+        /// the size of the screen buffer.
+        buffer_size: Coord,
+    },
+    /// The rectangle is empty, its right or bottom side lies before its left or top side, or it reaches outside of the screen buffer.
+    InvalidRegion(WindowPositions),
+    /// The size has a negative axis, or an axis of 0 where at least one cell is needed, like for the size of the window.
+    InvalidSize(Coord),
+    /// The window size exceeds the largest window that fits on the screen, see `Console::largest_window_size`.
+    WindowTooLarge {
+        /// the requested size of the window.
+        size: Coord,
+        /// the largest possible size of the window.
+        largest: Coord,
+    },
+    /// The number of cells of the buffer doesn't match its size.
+    BufferLength {
+        /// the number of cells in the buffer.
+        len: usize,
+        /// the width and height the buffer should have.
+        size: Coord,
+    },
+    /// The position lies outside of the visible window.
+    OutsideWindow(Coord),
+    /// A number lies outside of the range the function accepts, like the frequency of `Console::beep`.
+    ValueOutOfRange {
+        /// the name of the argument.
+        argument: &'static str,
+        /// the rejected value.
+        value: u32,
+        /// the lowest accepted value.
+        min: u32,
+        /// the highest accepted value.
+        max: u32,
+    },
+}
+
+impl ConsoleError {
+    /// Get the `io::ErrorKind` this error is reported with as an `io::Error`.
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            ConsoleError::Io(e) => e.kind(),
+            ConsoleError::Utf8(_) => io::ErrorKind::InvalidData,
+            _ => io::ErrorKind::InvalidInput,
+        }
+    }
+}
+
+impl fmt::Display for ConsoleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConsoleError::Io(e) => write!(f, "{}", e),
+            ConsoleError::InvalidCoord(coord) => write!(
+                f,
+                "Coordinate ({}, {}) is outside of the screen buffer",
+                coord.x, coord.y
+            ),
+            ConsoleError::WrongHandleType {
+                function,
+                handle_type,
+                needs_input,
+            } => {
+                write!(
+                    f,
+                    "`{}` requires a console {} handle",
+                    function,
+                    if *needs_input { "input" } else { "output" }
+                )?;
+
+                match handle_type {
+                    Some(handle_type) => write!(
+                        f,
+                        ", but this console was created with `HandleType::{:?}`",
+                        handle_type
+                    ),
+                    None => Ok(()),
+                }
+            }
+            ConsoleError::Utf8(e) => write!(f, "{}", e),
+            ConsoleError::CharSpansMultipleCells(e) => write!(f, "{}", e),
+            ConsoleError::FillOutOfRange {
+                start,
+                count,
+                buffer_size,
+            } => write!(
+                f,
+                "Filling {} cells from ({}, {}) exceeds the screen buffer of {}x{} cells",
+                count, start.x, start.y, buffer_size.x, buffer_size.y
+            ),
+            ConsoleError::InvalidRegion(rect) => write!(
+                f,
+                "Region left {}, top {}, right {}, bottom {} is empty or outside of the screen buffer",
+                rect.left, rect.top, rect.right, rect.bottom
+            ),
+            ConsoleError::InvalidSize(size) => write!(f, "Invalid size {}x{}", size.x, size.y),
+            ConsoleError::WindowTooLarge { size, largest } => write!(
+                f,
+                "Window size {}x{} exceeds the largest possible window size {}x{}",
+                size.x, size.y, largest.x, largest.y
+            ),
+            ConsoleError::BufferLength { len, size } => write!(
+                f,
+                "Buffer of {} cells doesn't match a size of {}x{}",
+                len, size.x, size.y
+            ),
+            ConsoleError::OutsideWindow(position) => write!(
+                f,
+                "Position ({}, {}) is outside of the visible window",
+                position.x, position.y
+            ),
+            ConsoleError::ValueOutOfRange {
+                argument,
+                value,
+                min,
+                max,
+            } => write!(
+                f,
+                "{} {} is outside of the range {} through {}",
+                argument, value, min, max
+            ),
+        }
+    }
+}
+
+impl Error for ConsoleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConsoleError::Io(e) => Some(e),
+            ConsoleError::Utf8(e) => Some(e),
+            ConsoleError::CharSpansMultipleCells(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for ConsoleError {
+    /// Recovers the `ConsoleError`, `Utf8Error` or `CharSpansMultipleCells` stored in an `io::Error`, any other error becomes `ConsoleError::Io`.
+    fn from(error: io::Error) -> Self {
+        match error.get_ref() {
+            Some(inner) if inner.is::<ConsoleError>() => {
+                *error.into_inner().unwrap().downcast().unwrap()
+            }
+            Some(inner) if inner.is::<Utf8Error>() => {
+                ConsoleError::Utf8(*error.into_inner().unwrap().downcast().unwrap())
+            }
+            Some(inner) if inner.is::<CharSpansMultipleCells>() => {
+                ConsoleError::CharSpansMultipleCells(
+                    *error.into_inner().unwrap().downcast().unwrap(),
+                )
+            }
+            _ => ConsoleError::Io(error),
+        }
+    }
+}
+
+impl From<Utf8Error> for ConsoleError {
+    fn from(error: Utf8Error) -> Self {
+        ConsoleError::Utf8(error)
+    }
+}
+
+impl From<ConsoleError> for io::Error {
+    fn from(error: ConsoleError) -> Self {
+        match error {
+            ConsoleError::Io(e) => e,
+            // keep the `Utf8Error` and `CharSpansMultipleCells` as the inner error, like the functions of this crate report them.
+            ConsoleError::Utf8(e) => io::Error::new(io::ErrorKind::InvalidData, e),
+            ConsoleError::CharSpansMultipleCells(e) => {
+                io::Error::new(io::ErrorKind::InvalidInput, e)
+            }
+            e => io::Error::new(e.kind(), e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+    use std::str;
+
+    use super::ConsoleError;
+    use crate::{CharSpansMultipleCells, Coord, HandleType};
+
+    #[test]
+    fn io_error_round_trip() {
+        let error = io::Error::from(ConsoleError::InvalidCoord(Coord::new(3, -1)));
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+
+        match ConsoleError::from(error) {
+            ConsoleError::InvalidCoord(coord) => assert_eq!(coord, Coord::new(3, -1)),
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        let error = io::Error::from(ConsoleError::WrongHandleType {
+            function: "ReadConsoleInputW",
+            handle_type: Some(HandleType::OutputHandle),
+            needs_input: true,
+        });
+        assert!(matches!(
+            ConsoleError::from(error),
+            ConsoleError::WrongHandleType {
+                handle_type: Some(HandleType::OutputHandle),
+                needs_input: true,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn char_spans_multiple_cells_errors() {
+        let error = io::Error::new(
+            io::ErrorKind::InvalidInput,
+            CharSpansMultipleCells::new('😀'),
+        );
+
+        match ConsoleError::from(error) {
+            ConsoleError::CharSpansMultipleCells(e) => assert_eq!(e.character(), '😀'),
+            e => panic!("unexpected error {:?}", e),
+        }
+
+        // converted back, the inner error is a `CharSpansMultipleCells` again.
+        let error = io::Error::from(ConsoleError::CharSpansMultipleCells(
+            CharSpansMultipleCells::new('漢'),
+        ));
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert!(error.get_ref().unwrap().is::<CharSpansMultipleCells>());
+    }
+
+    #[test]
+    fn validation_errors_are_invalid_input() {
+        let error = io::Error::from(ConsoleError::ValueOutOfRange {
+            argument: "beep frequency",
+            value: 20,
+            min: 37,
+            max: 32767,
+        });
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(
+            error.to_string(),
+            "beep frequency 20 is outside of the range 37 through 32767"
+        );
+        assert!(matches!(
+            ConsoleError::from(error),
+            ConsoleError::ValueOutOfRange { value: 20, .. }
+        ));
+    }
+
+    #[test]
+    fn other_io_errors() {
+        let error = ConsoleError::from(io::Error::new(io::ErrorKind::Other, "failed"));
+        assert_eq!(error.kind(), io::ErrorKind::Other);
+        assert!(matches!(error, ConsoleError::Io(_)));
+    }
+
+    #[test]
+    fn utf8_errors() {
+        let invalid = vec![b'a', 0xff];
+        let utf8_error = str::from_utf8(&invalid).unwrap_err();
+        let error = io::Error::from(ConsoleError::from(utf8_error));
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(matches!(ConsoleError::from(error), ConsoleError::Utf8(_)));
+    }
+}
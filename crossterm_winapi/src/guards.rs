@@ -0,0 +1,101 @@
+//! This module contains guards that restore some console state when they go out of scope.
+
+use std::io::Result;
+
+use super::{Console, ConsoleMode, Handle};
+
+/// Restores the text attributes of the console to the attributes captured at creation when dropped.
+///
+/// Create one with `Console::attribute_guard`.
+#[must_use = "the attributes are restored as soon as the guard is dropped"]
+pub struct AttributeGuard {
+    handle: Handle,
+    original: u16,
+}
+
+impl AttributeGuard {
+    pub(crate) fn new(handle: Handle, original: u16) -> AttributeGuard {
+        AttributeGuard { handle, original }
+    }
+
+    /// The attributes that will be restored when this guard is dropped.
+    pub fn original_attributes(&self) -> u16 {
+        self.original
+    }
+}
+
+impl Drop for AttributeGuard {
+    fn drop(&mut self) {
+        let _ = Console::from(Handle::from(*self.handle)).set_text_attribute(self.original);
+    }
+}
+
+/// Restores the console mode that was active before raw mode was entered when dropped.
+///
+/// Create one with `Console::enter_raw_mode`.
+/// Errors while restoring the mode are ignored on drop, call `disable` to handle them.
+#[must_use = "raw mode is disabled as soon as the guard is dropped"]
+pub struct RawModeGuard {
+    handle: Handle,
+    original_mode: u32,
+    restored: bool,
+}
+
+impl RawModeGuard {
+    pub(crate) fn new(handle: Handle, original_mode: u32) -> RawModeGuard {
+        RawModeGuard {
+            handle,
+            original_mode,
+            restored: false,
+        }
+    }
+
+    /// Restores the console mode that was active before raw mode was entered.
+    pub fn disable(mut self) -> Result<()> {
+        self.restored = true;
+        ConsoleMode::from(*self.handle).set_mode(self.original_mode)
+    }
+}
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = ConsoleMode::from(*self.handle).set_mode(self.original_mode);
+        }
+    }
+}
+
+/// Restores the console mode that was active before mouse capture was enabled when dropped.
+///
+/// Create one with `Console::enable_mouse_capture`.
+/// Errors while restoring the mode are ignored on drop, call `disable` to handle them.
+#[must_use = "mouse capture is disabled as soon as the guard is dropped"]
+pub struct MouseCaptureGuard {
+    handle: Handle,
+    original_mode: u32,
+    restored: bool,
+}
+
+impl MouseCaptureGuard {
+    pub(crate) fn new(handle: Handle, original_mode: u32) -> MouseCaptureGuard {
+        MouseCaptureGuard {
+            handle,
+            original_mode,
+            restored: false,
+        }
+    }
+
+    /// Restores the console mode that was active before mouse capture was enabled.
+    pub fn disable(mut self) -> Result<()> {
+        self.restored = true;
+        ConsoleMode::from(*self.handle).set_mode(self.original_mode)
+    }
+}
+
+impl Drop for MouseCaptureGuard {
+    fn drop(&mut self) {
+        if !self.restored {
+            let _ = ConsoleMode::from(*self.handle).set_mode(self.original_mode);
+        }
+    }
+}
@@ -4,47 +4,109 @@ use std::io::{self, Result};
 use std::ops::Deref;
 use std::ptr::null_mut;
 
+use winapi::shared::minwindef::FALSE;
 use winapi::um::{
+    consoleapi::GetConsoleMode,
     fileapi::{CreateFileW, OPEN_EXISTING},
-    handleapi::INVALID_HANDLE_VALUE,
+    handleapi::{CloseHandle, DuplicateHandle, INVALID_HANDLE_VALUE},
     processenv::GetStdHandle,
+    processthreadsapi::GetCurrentProcess,
     winbase::{STD_INPUT_HANDLE, STD_OUTPUT_HANDLE},
-    winnt::{FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, GENERIC_WRITE, HANDLE},
+    winnt::{
+        DUPLICATE_SAME_ACCESS, FILE_SHARE_READ, FILE_SHARE_WRITE, GENERIC_READ, GENERIC_WRITE,
+        HANDLE,
+    },
 };
 
+use super::is_true;
+
 /// This enum represents the different handles that could be requested.
 ///
 /// Some more details could be found [here](https://docs.microsoft.com/en-us/windows/console/getstdhandle#parameters)
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub enum HandleType {
     /// This represents the `STD_OUTPUT_HANDLE`
+    ///
+    /// When the standard output is redirected to a file or pipe, this handle is no console handle and console functions will fail on it.
     OutputHandle,
     /// This represents the `STD_INPUT_HANDLE`
+    ///
+    /// When the standard input is redirected from a file or pipe, this handle is no console handle and console functions will fail on it.
     InputHandle,
     /// This represents the `CONOUT$` file handle
     /// When using multiple screen buffers this will always point to the to the current screen output buffer.
+    ///
+    /// This handle always refers to the console, even when the standard output is redirected.
     CurrentOutputHandle,
     /// This represents the `CONIN$` file handle.
     /// When using multiple screen buffers this will always point to the to the current screen input buffer.
+    ///
+    /// This handle always refers to the console, even when the standard input is redirected.
     CurrentInputHandle,
 }
 
 /// This abstracts away some WinaApi calls to set and get some console handles.
 ///
+/// A `Handle` only closes the `HANDLE` it wraps when it owns it, see `is_owned`:
+/// - The standard handles, `HandleType::OutputHandle` and `HandleType::InputHandle`, are shared by the whole process and are never closed.
+/// - Raw handles passed to `Handle::from` are never closed either, the code that created them stays responsible for them.
+/// - Handles that are created for this `Handle`, like `CONOUT$` and `CONIN$` opened for the `Current*` handle types,
+///   duplicates made by `try_clone` and screen buffers made by `Console::create_alternate`, are closed when the `Handle` is dropped.
+///
+/// Raw copies taken from an owned `Handle` are only valid for as long as it is alive.
+///
 // Wraps the underlying WinApi type: [HANDLE]
 pub struct Handle {
     handle: HANDLE,
+    // the type this handle was requested with, `None` for raw handles.
+    handle_type: Option<HandleType>,
+    // whether the handle was created for this instance and has to be closed by it.
+    owned: bool,
+}
+
+impl HandleType {
+    /// Checks if this type refers to the console input buffer, instead of a screen buffer.
+    pub fn is_input(&self) -> bool {
+        match self {
+            HandleType::InputHandle | HandleType::CurrentInputHandle => true,
+            HandleType::OutputHandle | HandleType::CurrentOutputHandle => false,
+        }
+    }
 }
 
 impl Handle {
-    pub fn new(handle: HandleType) -> Result<Handle> {
-        let handle = match handle {
+    pub fn new(handle_type: HandleType) -> Result<Handle> {
+        let handle = match handle_type {
             HandleType::OutputHandle => Handle::output_handle(),
             HandleType::InputHandle => Handle::input_handle(),
             HandleType::CurrentOutputHandle => Handle::current_out_handle(),
             HandleType::CurrentInputHandle => Handle::current_in_handle(),
         }?;
 
-        Ok(Handle { handle })
+        Ok(Handle {
+            handle,
+            handle_type: Some(handle_type),
+            owned: match handle_type {
+                HandleType::CurrentOutputHandle | HandleType::CurrentInputHandle => true,
+                HandleType::OutputHandle | HandleType::InputHandle => false,
+            },
+        })
+    }
+
+    /// Wraps a handle that was created by this crate, so that it is closed when the `Handle` is dropped.
+    pub(crate) fn owned(handle: HANDLE) -> Handle {
+        Handle {
+            handle,
+            handle_type: None,
+            owned: true,
+        }
+    }
+
+    /// Get the type this handle was created with by `Handle::new`.
+    ///
+    /// Handles created from a raw `HANDLE` return `None`, since their type is unknown.
+    pub fn handle_type(&self) -> Option<HandleType> {
+        self.handle_type
     }
 
     /// Get the handle of the active screen buffer.
@@ -73,7 +135,6 @@ impl Handle {
         };
 
         if !Handle::is_valid_handle(&handle) {
-            println!("invalid!!");
             return Err(io::Error::last_os_error());
         }
 
@@ -148,6 +209,77 @@ impl Handle {
         }
     }
 
+    /// Creates a new handle that refers to the same console object as this handle.
+    ///
+    /// The duplicate is independent of this handle and can be moved into another thread,
+    /// for example to read input on one thread while writing output on another.
+    /// It is a separate handle that is owned by the returned `Handle`, so it is closed when that is dropped.
+    ///
+    /// Wraps the underlying function call: [DuplicateHandle]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/handleapi/nf-handleapi-duplicatehandle]
+    pub fn try_clone(&self) -> Result<Handle> {
+        let mut handle = INVALID_HANDLE_VALUE;
+
+        unsafe {
+            let process = GetCurrentProcess();
+
+            if !is_true(DuplicateHandle(
+                process,
+                self.handle,
+                process,
+                &mut handle,
+                0,
+                FALSE,
+                DUPLICATE_SAME_ACCESS,
+            )) {
+                return Err(io::Error::last_os_error());
+            }
+        }
+
+        Ok(Handle {
+            handle,
+            handle_type: self.handle_type,
+            owned: true,
+        })
+    }
+
+    /// Checks if this `Handle` owns the `HANDLE` it wraps, in that case it is closed when this `Handle` is dropped or closed.
+    pub fn is_owned(&self) -> bool {
+        self.owned
+    }
+
+    /// Closes the handle, unlike dropping it this reports when closing fails.
+    ///
+    /// Handles that are not owned by this `Handle`, like the standard handles, are left open and `Ok` is returned.
+    ///
+    /// Wraps the underlying function call: [CloseHandle]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/handleapi/nf-handleapi-closehandle]
+    pub fn close(mut self) -> Result<()> {
+        if !self.owned {
+            return Ok(());
+        }
+
+        // the handle is closed here, so it must not be closed again on drop.
+        self.owned = false;
+
+        if !is_true(unsafe { CloseHandle(self.handle) }) {
+            return Err(io::Error::last_os_error());
+        }
+
+        Ok(())
+    }
+
+    /// Checks if this handle refers to a console, instead of for example a redirected file or pipe.
+    ///
+    /// Any failure to get the console mode, like `ERROR_INVALID_HANDLE`, is treated as not being a console.
+    ///
+    /// Wraps the underlying function call: [GetConsoleMode]
+    /// link: [https://docs.microsoft.com/en-us/windows/console/getconsolemode]
+    pub fn is_console(&self) -> bool {
+        let mut mode = 0;
+        is_true(unsafe { GetConsoleMode(self.handle, &mut mode) })
+    }
+
     /// Checks if the console handle is an invalid handle value.
     ///
     /// This is done by checking if the passed `HANDLE` is equal to `INVALID_HANDLE_VALUE`
@@ -160,6 +292,9 @@ impl Handle {
     }
 }
 
+// Console handles can be used from any thread.
+unsafe impl Send for Handle {}
+
 impl Deref for Handle {
     type Target = HANDLE;
 
@@ -170,7 +305,21 @@ impl Deref for Handle {
 
 impl From<HANDLE> for Handle {
     fn from(handle: HANDLE) -> Self {
-        Handle { handle }
+        Handle {
+            handle,
+            handle_type: None,
+            owned: false,
+        }
+    }
+}
+
+impl Drop for Handle {
+    fn drop(&mut self) {
+        if self.owned {
+            unsafe {
+                CloseHandle(self.handle);
+            }
+        }
     }
 }
 
@@ -185,4 +334,54 @@ mod test {
         assert!(Handle::new(HandleType::CurrentOutputHandle).is_ok());
         assert!(Handle::new(HandleType::CurrentInputHandle).is_ok());
     }
+
+    #[test]
+    fn console_handle_is_console() {
+        assert!(Handle::new(HandleType::CurrentOutputHandle)
+            .unwrap()
+            .is_console());
+        assert!(!Handle::from(std::ptr::null_mut()).is_console());
+    }
+
+    #[test]
+    fn clone_handle() {
+        let handle = Handle::new(HandleType::CurrentOutputHandle).unwrap();
+        let clone = handle.try_clone().unwrap();
+
+        assert!(Handle::is_valid_handle(&clone));
+        assert_ne!(*handle, *clone);
+        assert_eq!(clone.handle_type(), Some(HandleType::CurrentOutputHandle));
+    }
+
+    #[test]
+    fn handle_ownership() {
+        assert!(!Handle::new(HandleType::OutputHandle).unwrap().is_owned());
+        assert!(!Handle::new(HandleType::InputHandle).unwrap().is_owned());
+
+        let handle = Handle::new(HandleType::CurrentOutputHandle).unwrap();
+        assert!(handle.is_owned());
+        assert!(!Handle::from(*handle).is_owned());
+
+        // closing a duplicate leaves the original usable.
+        let clone = handle.try_clone().unwrap();
+        assert!(clone.is_owned());
+        clone.close().unwrap();
+        assert!(handle.is_console());
+
+        handle.close().unwrap();
+        Handle::new(HandleType::OutputHandle)
+            .unwrap()
+            .close()
+            .unwrap();
+    }
+
+    #[test]
+    fn handle_type_is_tracked() {
+        let handle = Handle::new(HandleType::InputHandle).unwrap();
+
+        assert_eq!(handle.handle_type(), Some(HandleType::InputHandle));
+        assert_eq!(Handle::from(*handle).handle_type(), None);
+        assert!(HandleType::CurrentInputHandle.is_input());
+        assert!(!HandleType::OutputHandle.is_input());
+    }
 }
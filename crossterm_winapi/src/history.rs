@@ -0,0 +1,108 @@
+//! This module provides access to the command history and aliases that the console keeps for each executable.
+//!
+//! It is only available with the `history` feature enabled.
+
+use std::io::{Error, Result};
+
+use winapi::shared::minwindef::DWORD;
+use winapi::um::{wincon::AddConsoleAliasW, winnt::LPWSTR};
+
+use super::is_true;
+
+#[link(name = "kernel32")]
+extern "system" {
+    // these functions are exported by `kernel32` but are missing from `winapi`.
+    fn GetConsoleCommandHistoryW(
+        Commands: LPWSTR,
+        CommandBufferLength: DWORD,
+        ExeName: LPWSTR,
+    ) -> DWORD;
+    fn GetConsoleCommandHistoryLengthW(ExeName: LPWSTR) -> DWORD;
+}
+
+/// Get the commands entered in the console for the given executable, like `cmd.exe`, oldest first.
+///
+/// An executable without any history results in an empty `Vec`, invalid UTF-16 is replaced with `U+FFFD`.
+///
+/// Wraps the underlying function calls: [GetConsoleCommandHistoryLengthW, GetConsoleCommandHistoryW]
+pub fn command_history(exe: &str) -> Result<Vec<String>> {
+    let mut exe = to_wide(exe);
+
+    // the length is in bytes, not in UTF-16 code units.
+    let length = unsafe { GetConsoleCommandHistoryLengthW(exe.as_mut_ptr()) };
+
+    if length == 0 {
+        return Ok(Vec::new());
+    }
+
+    let mut buffer = vec![0u16; length as usize / 2];
+    let written =
+        unsafe { GetConsoleCommandHistoryW(buffer.as_mut_ptr(), length, exe.as_mut_ptr()) };
+
+    if written == 0 {
+        return Err(Error::last_os_error());
+    }
+
+    buffer.truncate(written as usize / 2);
+
+    Ok(split_commands(&buffer))
+}
+
+/// Defines an alias for the given executable, like `cmd.exe`, so that typing `source` runs `target`.
+///
+/// An empty `target` removes the alias.
+///
+/// Wraps the underlying function call: [AddConsoleAliasW]
+/// link: [https://docs.microsoft.com/en-us/windows/console/addconsolealias]
+pub fn add_alias(source: &str, target: &str, exe: &str) -> Result<()> {
+    let mut source = to_wide(source);
+    let mut target = to_wide(target);
+    let mut exe = to_wide(exe);
+
+    // a null target removes the alias, an empty string is not accepted.
+    let target_ptr = if target.len() == 1 {
+        std::ptr::null_mut()
+    } else {
+        target.as_mut_ptr()
+    };
+
+    unsafe {
+        if !is_true(AddConsoleAliasW(
+            source.as_mut_ptr(),
+            target_ptr,
+            exe.as_mut_ptr(),
+        )) {
+            return Err(Error::last_os_error());
+        }
+    }
+
+    Ok(())
+}
+
+/// Encodes the string as a null terminated UTF-16 string.
+fn to_wide(string: &str) -> Vec<u16> {
+    string.encode_utf16().chain(Some(0)).collect()
+}
+
+/// Splits the null separated commands returned by `GetConsoleCommandHistoryW`.
+fn split_commands(buffer: &[u16]) -> Vec<String> {
+    buffer
+        .split(|unit| *unit == 0)
+        .filter(|command| !command.is_empty())
+        .map(String::from_utf16_lossy)
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::{split_commands, to_wide};
+
+    #[test]
+    fn split_history_buffer() {
+        let mut buffer = to_wide("dir");
+        buffer.extend(to_wide("echo hello"));
+
+        assert_eq!(split_commands(&buffer), vec!["dir", "echo hello"]);
+        assert!(split_commands(&[]).is_empty());
+    }
+}
@@ -1,23 +1,67 @@
 #![deny(unused_imports)]
 
+#[cfg(windows)]
 pub use self::{
-    console::Console,
+    batch::ConsoleBatch,
+    console::{CharsWritten, Console, ConsoleEvents, ConsoleReader, CtrlEvent, ScreenSnapshot},
+    console_color::{attributes, ConsoleColor},
     console_mode::ConsoleMode,
     csbi::ScreenBufferInfo,
+    error::ConsoleError,
+    guards::{AttributeGuard, MouseCaptureGuard, RawModeGuard},
     handle::{Handle, HandleType},
+    resize::ResizeWatcher,
     screen_buffer::ScreenBuffer,
     structs::{
-        ButtonState, ControlKeyState, Coord, EventFlags, InputEventType, InputRecord,
-        KeyEventRecord, MouseEvent, Size, WindowPositions,
+        ButtonState, CharInfo, CharSpansMultipleCells, ControlKeyState, Coord, Event, EventFlags,
+        FontInfo, InputEventType, InputRecord, KeyEventRecord, MouseEvent, MouseKind, ResizeEvent,
+        ScreenBuffer2D, SelectionFlags, SelectionInfo, Size, WindowPositions,
     },
+    vt::VtCaps,
 };
 
+#[cfg(all(windows, feature = "async"))]
+pub use self::readable::ReadableFuture;
+pub use self::width::char_cell_width;
+
+#[cfg(not(windows))]
+pub use self::{
+    structs::Coord,
+    unsupported::{Console, Handle, HandleType},
+};
+
+#[cfg(windows)]
+mod batch;
+#[cfg(windows)]
 mod console;
+#[cfg(windows)]
+mod console_color;
+#[cfg(windows)]
 mod console_mode;
+#[cfg(windows)]
 mod csbi;
+#[cfg(windows)]
+mod error;
+#[cfg(windows)]
+mod guards;
+#[cfg(windows)]
 mod handle;
+#[cfg(all(windows, feature = "history"))]
+pub mod history;
+#[cfg(all(windows, feature = "async"))]
+mod readable;
+#[cfg(windows)]
+mod resize;
+#[cfg(windows)]
 mod screen_buffer;
 mod structs;
+#[cfg(all(windows, test))]
+mod testutil;
+#[cfg(not(windows))]
+mod unsupported;
+#[cfg(windows)]
+mod vt;
+mod width;
 
 /// Parses the given integer to an bool by checking if the value is 0 or 1.
 /// This is currently used for checking if a WinApi called succeeded, this might be moved into a macro at some time.
@@ -0,0 +1,147 @@
+//! This module contains the future that completes when the console input buffer has events to read.
+//!
+//! It is only available with the `async` feature enabled.
+
+use std::future::Future;
+use std::io::{Error, Result};
+use std::pin::Pin;
+use std::ptr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::task::{Context, Poll, Waker};
+
+use winapi::shared::ntdef::{BOOLEAN, PVOID};
+use winapi::um::{
+    handleapi::INVALID_HANDLE_VALUE,
+    threadpoollegacyapiset::UnregisterWaitEx,
+    winbase::{RegisterWaitForSingleObject, INFINITE},
+    winnt::{HANDLE, WT_EXECUTEINWAITTHREAD, WT_EXECUTEONLYONCE},
+};
+
+use super::{is_true, Console};
+
+/// The state that is shared with the wait callback, which runs on a thread of the system thread pool.
+struct Shared {
+    // set by the callback, the registered wait is finished then and has to be registered again.
+    fired: AtomicBool,
+    waker: Mutex<Option<Waker>>,
+}
+
+/// A future that completes when the input buffer of the console has events to read, without reading them.
+///
+/// Create one with `Console::readable`. Instead of blocking a thread, the input handle is waited on by the system
+/// thread pool with `RegisterWaitForSingleObject`, which wakes the task when the handle is signaled.
+/// Dropping the future unregisters the wait, so it can be cancelled at any time.
+///
+/// Another reader could take the events before the task is polled again, so the input buffer is checked on every poll.
+#[must_use = "futures do nothing unless polled"]
+pub struct ReadableFuture<'a> {
+    console: &'a Console,
+    // the registered wait, `None` before the first poll and after completing.
+    wait: Option<HANDLE>,
+    // boxed so its address stays the same while the callback could use it.
+    shared: Box<Shared>,
+}
+
+impl<'a> ReadableFuture<'a> {
+    pub(crate) fn new(console: &'a Console) -> ReadableFuture<'a> {
+        ReadableFuture {
+            console,
+            wait: None,
+            shared: Box::new(Shared {
+                fired: AtomicBool::new(false),
+                waker: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Registers a wait on the input handle that calls `wake` once when the handle is signaled.
+    ///
+    /// Wraps the underlying function call: [RegisterWaitForSingleObject]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/api/winbase/nf-winbase-registerwaitforsingleobject]
+    fn register(&mut self) -> Result<()> {
+        let input = self.console.input_handle_raw()?;
+        let mut wait = ptr::null_mut();
+        let context = &*self.shared as *const Shared as PVOID;
+
+        unsafe {
+            if !is_true(RegisterWaitForSingleObject(
+                &mut wait,
+                input,
+                Some(wake),
+                context,
+                INFINITE,
+                WT_EXECUTEONLYONCE | WT_EXECUTEINWAITTHREAD,
+            )) {
+                return Err(Error::last_os_error());
+            }
+        }
+
+        self.wait = Some(wait);
+        Ok(())
+    }
+
+    /// Unregisters the wait, if any, and waits for a running callback to finish, so `shared` isn't used anymore afterwards.
+    ///
+    /// Wraps the underlying function call: [UnregisterWaitEx]
+    /// link: [https://docs.microsoft.com/en-us/windows/desktop/sync/unregisterwaitex]
+    fn unregister(&mut self) {
+        if let Some(wait) = self.wait.take() {
+            unsafe {
+                UnregisterWaitEx(wait, INVALID_HANDLE_VALUE);
+            }
+        }
+
+        self.shared.fired.store(false, Ordering::SeqCst);
+    }
+}
+
+impl Future for ReadableFuture<'_> {
+    type Output = Result<()>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Result<()>> {
+        let this = self.get_mut();
+
+        match this.console.wait_for_input(0) {
+            Ok(true) => {
+                this.unregister();
+                return Poll::Ready(Ok(()));
+            }
+            Ok(false) => {}
+            Err(e) => {
+                this.unregister();
+                return Poll::Ready(Err(e));
+            }
+        }
+
+        // store the waker before checking `fired`, so a callback that runs after the check wakes this task.
+        *this.shared.waker.lock().unwrap() = Some(cx.waker().clone());
+
+        if this.wait.is_none() || this.shared.fired.load(Ordering::SeqCst) {
+            // the events that signaled the previous wait were read by someone else, wait again.
+            this.unregister();
+
+            if let Err(e) = this.register() {
+                return Poll::Ready(Err(e));
+            }
+        }
+
+        Poll::Pending
+    }
+}
+
+impl Drop for ReadableFuture<'_> {
+    fn drop(&mut self) {
+        self.unregister();
+    }
+}
+
+/// The callback of the registered wait, `context` points to the `Shared` state of the future.
+unsafe extern "system" fn wake(context: PVOID, _timed_out: BOOLEAN) {
+    let shared = &*(context as *const Shared);
+    shared.fired.store(true, Ordering::SeqCst);
+
+    if let Some(waker) = shared.waker.lock().unwrap().take() {
+        waker.wake();
+    }
+}
@@ -0,0 +1,85 @@
+//! This module contains the watcher that reports resizes of the console from a background thread.
+
+use std::io::{Error, Result};
+use std::ptr;
+use std::thread::{self, JoinHandle};
+
+use winapi::shared::minwindef::{FALSE, TRUE};
+use winapi::um::{
+    synchapi::{CreateEventW, SetEvent, WaitForMultipleObjects},
+    winbase::{INFINITE, WAIT_OBJECT_0},
+};
+
+use super::{Console, Coord, Event, Handle};
+
+/// Calls a callback for every resize of the console from a background thread, until it is dropped.
+///
+/// Create one with `Console::on_resize`. Dropping the watcher stops the thread and waits for it to finish,
+/// so the callback is never called after the drop.
+#[must_use = "the watcher stops as soon as it is dropped"]
+pub struct ResizeWatcher {
+    // a manual reset event that is signaled to stop the thread.
+    stop: Handle,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl ResizeWatcher {
+    /// Spawns the thread that reads the input records of `input` and calls `callback` with the new screen buffer size of each resize event.
+    pub(crate) fn spawn(
+        input: Handle,
+        mut callback: impl FnMut(Coord) + Send + 'static,
+    ) -> Result<ResizeWatcher> {
+        let event = unsafe { CreateEventW(ptr::null_mut(), TRUE, FALSE, ptr::null()) };
+
+        if event.is_null() {
+            return Err(Error::last_os_error());
+        }
+
+        let stop = Handle::owned(event);
+        let thread_stop = stop.try_clone()?;
+
+        let thread = thread::spawn(move || {
+            let handles = [*thread_stop, *input];
+            let console = Console::from(input);
+
+            loop {
+                let signaled = unsafe {
+                    WaitForMultipleObjects(handles.len() as u32, handles.as_ptr(), FALSE, INFINITE)
+                };
+
+                // the stop event comes first, so it wins when both are signaled.
+                if signaled != WAIT_OBJECT_0 + 1 {
+                    break;
+                }
+
+                let records = match console.read_console_input() {
+                    Ok((_, records)) => records,
+                    Err(_) => break,
+                };
+
+                for record in records {
+                    if let Event::WindowBufferSize(size) = record.event() {
+                        callback(size);
+                    }
+                }
+            }
+        });
+
+        Ok(ResizeWatcher {
+            stop,
+            thread: Some(thread),
+        })
+    }
+}
+
+impl Drop for ResizeWatcher {
+    fn drop(&mut self) {
+        unsafe {
+            SetEvent(*self.stop);
+        }
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -32,6 +32,8 @@ impl ScreenBuffer {
 
     /// Create new console screen buffer.
     ///
+    /// The handle of the new buffer is not closed when the `ScreenBuffer` is dropped, so it stays usable after it is shown.
+    ///
     /// Wraps the underlying function call: [CreateConsoleScreenBuffer]
     /// link: [https://docs.microsoft.com/en-us/windows/console/createconsolescreenbuffer]
     pub fn create() -> ScreenBuffer {
@@ -1,12 +1,34 @@
+#[cfg(windows)]
+pub use self::char_info::{CharInfo, CharSpansMultipleCells};
 pub use self::coord::Coord;
+#[cfg(windows)]
+pub use self::font_info::FontInfo;
+#[cfg(windows)]
 pub use self::input::{
-    ButtonState, ControlKeyState, EventFlags, InputEventType, InputRecord, KeyEventRecord,
-    MouseEvent,
+    ButtonState, ControlKeyState, Event, EventFlags, InputEventType, InputRecord, KeyEventRecord,
+    MouseEvent, MouseKind, ResizeEvent,
 };
+#[cfg(windows)]
+pub use self::screen_buffer_2d::ScreenBuffer2D;
+#[cfg(windows)]
+pub use self::selection_info::{SelectionFlags, SelectionInfo};
+#[cfg(windows)]
 pub use self::size::Size;
+#[cfg(windows)]
 pub use self::window_coords::WindowPositions;
 
+#[cfg(windows)]
+mod char_info;
 mod coord;
+#[cfg(windows)]
+mod font_info;
+#[cfg(windows)]
 mod input;
+#[cfg(windows)]
+mod screen_buffer_2d;
+#[cfg(windows)]
+mod selection_info;
+#[cfg(windows)]
 mod size;
+#[cfg(windows)]
 mod window_coords;
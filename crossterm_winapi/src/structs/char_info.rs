@@ -0,0 +1,97 @@
+//! This module provides a type that represents a single cell of the screen buffer.
+//! For example, in WinAPi we have `CHAR_INFO` which stores the character as an union.
+//! This module provides some trait implementations who will make parsing and working whit `CHAR_INFO` easier.
+
+use std::char;
+use std::error::Error;
+use std::fmt;
+use std::mem::zeroed;
+
+use winapi::um::wincon::CHAR_INFO;
+
+/// This type represents a single character cell of the screen buffer, the character and its attributes.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CharInfo {
+    /// The character in this cell.
+    pub ch: char,
+    /// The attributes (colors) of this cell.
+    pub attributes: u16,
+}
+
+impl CharInfo {
+    /// Create a new cell instance by passing in the character and attributes.
+    pub fn new(ch: char, attributes: u16) -> CharInfo {
+        CharInfo { ch, attributes }
+    }
+}
+
+impl From<CHAR_INFO> for CharInfo {
+    /// Characters that are not valid on their own, like a lone surrogate, are converted to `U+FFFD`.
+    fn from(char_info: CHAR_INFO) -> Self {
+        let utf16 = unsafe { *char_info.Char.UnicodeChar() };
+
+        CharInfo {
+            ch: char::from_u32(u32::from(utf16)).unwrap_or(char::REPLACEMENT_CHARACTER),
+            attributes: char_info.Attributes,
+        }
+    }
+}
+
+impl From<CharInfo> for CHAR_INFO {
+    /// A cell can only hold a single UTF-16 code unit, characters that need a surrogate pair are converted to `U+FFFD`.
+    fn from(char_info: CharInfo) -> Self {
+        let mut utf16 = [0u16; 2];
+        let utf16 = match char_info.ch.encode_utf16(&mut utf16) {
+            &mut [unit] => unit,
+            _ => char::REPLACEMENT_CHARACTER as u16,
+        };
+
+        let mut raw: CHAR_INFO = unsafe { zeroed() };
+        unsafe {
+            *raw.Char.UnicodeChar_mut() = utf16;
+        }
+        raw.Attributes = char_info.attributes;
+        raw
+    }
+}
+
+/// The error for a character that has to fit in a single cell, but is encoded as a UTF-16 surrogate pair, like `U+1F600` (😀),
+/// or is displayed two cells wide, like `U+6F22` (漢), see `char_cell_width`.
+///
+/// A cell holds a single UTF-16 code unit, so a surrogate pair can't be written into one cell without corrupting it.
+/// A wide character does fit in a cell, but it is drawn over the cell after it as well, so writing it into each cell doesn't line up.
+/// It is returned as the inner error of an `io::Error` of kind `InvalidInput`, use `get_ref` and `downcast_ref` to get it,
+/// or convert the `io::Error` into a `ConsoleError`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct CharSpansMultipleCells {
+    character: char,
+}
+
+impl CharSpansMultipleCells {
+    pub(crate) fn new(character: char) -> CharSpansMultipleCells {
+        CharSpansMultipleCells { character }
+    }
+
+    /// Get the character that didn't fit in a single cell.
+    pub fn character(&self) -> char {
+        self.character
+    }
+}
+
+impl fmt::Display for CharSpansMultipleCells {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = if self.character.len_utf16() > 1 {
+            "needs a surrogate pair"
+        } else {
+            "is displayed two cells wide"
+        };
+
+        write!(
+            f,
+            "Character U+{:04X} {} and does not fit in a single cell",
+            self.character as u32, reason
+        )
+    }
+}
+
+impl Error for CharSpansMultipleCells {}
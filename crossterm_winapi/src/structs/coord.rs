@@ -2,10 +2,23 @@
 //! For example, in WinAPi we have `COORD` which looks and feels inconvenient.
 //! This module provides also some trait implementations who will make parsing and working whit `COORD` easier.
 
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::io;
+use std::ops::{Add, Sub};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(windows)]
 use winapi::um::wincon::COORD;
 
 /// This is type represents the position of something on a certain 'x' and 'y'.
-#[derive(Copy, Clone, Debug, Default, Eq, PartialOrd, PartialEq)]
+///
+/// Coordinates are ordered in reading order: `a < b` means that `a` is on an earlier row than `b`,
+/// or on the same row but further to the left.
+/// The default coordinate is `Coord::ORIGIN`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct Coord {
     /// the position on the x axis
     pub x: i16,
@@ -14,18 +27,69 @@ pub struct Coord {
 }
 
 impl Coord {
+    /// The upper-left corner of the screen buffer, where the cursor is after a clear.
+    pub const ORIGIN: Coord = Coord { x: 0, y: 0 };
+
     /// Create a new size instance by passing in the width and height.
     pub fn new(x: i16, y: i16) -> Coord {
         Coord { x, y }
     }
+
+    /// Clamps this coordinate into a buffer of the given size, `x` into `0..size.x` and `y` into `0..size.y`.
+    ///
+    /// An axis of `size` that is zero or negative leaves no valid values, that axis is clamped to 0.
+    pub fn clamp_to(self, size: Coord) -> Coord {
+        Coord::new(
+            self.x.min(size.x.saturating_sub(1)).max(0),
+            self.y.min(size.y.saturating_sub(1)).max(0),
+        )
+    }
+
+    /// Adds both axes of `other` to this coordinate, clamping each axis to `i16::MAX` and `i16::MIN` instead of overflowing.
+    pub fn saturating_add(self, other: Coord) -> Coord {
+        Coord::new(
+            self.x.saturating_add(other.x),
+            self.y.saturating_add(other.y),
+        )
+    }
 }
 
+impl Add for Coord {
+    type Output = Coord;
+
+    fn add(self, other: Coord) -> Coord {
+        Coord::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Coord {
+    type Output = Coord;
+
+    fn sub(self, other: Coord) -> Coord {
+        Coord::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Ord for Coord {
+    fn cmp(&self, other: &Coord) -> Ordering {
+        (self.y, self.x).cmp(&(other.y, other.x))
+    }
+}
+
+impl PartialOrd for Coord {
+    fn partial_cmp(&self, other: &Coord) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+#[cfg(windows)]
 impl From<COORD> for Coord {
     fn from(coord: COORD) -> Self {
         Coord::new(coord.X, coord.Y)
     }
 }
 
+#[cfg(windows)]
 impl From<Coord> for COORD {
     fn from(location: Coord) -> Self {
         COORD {
@@ -35,8 +99,85 @@ impl From<Coord> for COORD {
     }
 }
 
-impl Into<(u16, u16)> for Coord {
-    fn into(self) -> (u16, u16) {
-        (self.x as u16, self.y as u16)
+impl TryFrom<(u16, u16)> for Coord {
+    type Error = io::Error;
+
+    /// Fails with `InvalidInput` if either value exceeds `i16::MAX`.
+    fn try_from((x, y): (u16, u16)) -> io::Result<Coord> {
+        match (i16::try_from(x), i16::try_from(y)) {
+            (Ok(x), Ok(y)) => Ok(Coord::new(x, y)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("coordinate ({}, {}) does not fit in an i16 Coord", x, y),
+            )),
+        }
+    }
+}
+
+impl TryFrom<Coord> for (u16, u16) {
+    type Error = io::Error;
+
+    /// Fails with `InvalidInput` if either axis is negative.
+    fn try_from(coord: Coord) -> io::Result<(u16, u16)> {
+        match (u16::try_from(coord.x), u16::try_from(coord.y)) {
+            (Ok(x), Ok(y)) => Ok((x, y)),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("coordinate ({}, {}) has a negative axis", coord.x, coord.y),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::TryFrom;
+
+    use super::Coord;
+
+    #[test]
+    fn arithmetic() {
+        assert_eq!(Coord::new(1, 2) + Coord::new(3, 4), Coord::new(4, 6));
+        assert_eq!(Coord::new(5, 5) - Coord::new(2, 3), Coord::new(3, 2));
+        assert_eq!(
+            Coord::new(i16::MAX - 1, 0).saturating_add(Coord::new(5, 1)),
+            Coord::new(i16::MAX, 1)
+        );
+    }
+
+    #[test]
+    fn clamp_to_size() {
+        let size = Coord::new(80, 25);
+
+        assert_eq!(Coord::new(10, 5).clamp_to(size), Coord::new(10, 5));
+        assert_eq!(Coord::new(-3, -1).clamp_to(size), Coord::ORIGIN);
+        assert_eq!(Coord::new(80, 25).clamp_to(size), Coord::new(79, 24));
+        assert_eq!(
+            Coord::new(i16::MAX, i16::MIN).clamp_to(size),
+            Coord::new(79, 0)
+        );
+        assert_eq!(Coord::new(5, 5).clamp_to(Coord::new(0, -2)), Coord::ORIGIN);
+    }
+
+    #[test]
+    fn origin_is_default() {
+        assert_eq!(Coord::default(), Coord::ORIGIN);
+        assert_eq!(Coord::ORIGIN, Coord::new(0, 0));
+    }
+
+    #[test]
+    fn reading_order() {
+        assert!(Coord::new(10, 0) < Coord::new(0, 1));
+        assert!(Coord::new(0, 1) < Coord::new(1, 1));
+        assert!(Coord::new(3, 2) > Coord::new(9, 1));
+        assert_eq!(Coord::new(4, 4).max(Coord::new(5, 3)), Coord::new(4, 4));
+    }
+
+    #[test]
+    fn u16_conversions() {
+        assert_eq!(Coord::try_from((3, 7)).unwrap(), Coord::new(3, 7));
+        assert!(Coord::try_from((40_000, 0)).is_err());
+        assert_eq!(<(u16, u16)>::try_from(Coord::new(3, 7)).unwrap(), (3, 7));
+        assert!(<(u16, u16)>::try_from(Coord::new(0, -1)).is_err());
     }
 }
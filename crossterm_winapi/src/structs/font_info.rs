@@ -0,0 +1,104 @@
+//! This module provides a type that represents the font of the console.
+//! For example, in WinAPi we have `CONSOLE_FONT_INFOEX` which stores the face name in a fixed size UTF-16 array.
+//! This module provides some trait implementations who will make parsing and working whit `CONSOLE_FONT_INFOEX` easier.
+
+use std::mem::size_of;
+
+use winapi::um::wincon::{CONSOLE_FONT_INFOEX, COORD};
+use winapi::um::wingdi::LF_FACESIZE;
+
+use super::Size;
+
+/// This is a wrapper for the font used by the console.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FontInfo {
+    /// the index of the font in the console's font table
+    pub index: u32,
+    /// the width and height of each character cell, in logical units
+    pub size: Size,
+    /// the font pitch and family, see `TEXTMETRIC.tmPitchAndFamily`
+    pub family: u32,
+    /// the font weight, from 100 to 1000, 400 is normal and 700 is bold
+    pub weight: u32,
+    /// the name of the typeface, like `Consolas`
+    pub face_name: String,
+}
+
+impl From<CONSOLE_FONT_INFOEX> for FontInfo {
+    fn from(info: CONSOLE_FONT_INFOEX) -> Self {
+        let len = info
+            .FaceName
+            .iter()
+            .position(|&unit| unit == 0)
+            .unwrap_or(LF_FACESIZE);
+
+        FontInfo {
+            index: info.nFont,
+            size: Size::from(info.dwFontSize),
+            family: info.FontFamily,
+            weight: info.FontWeight,
+            face_name: String::from_utf16_lossy(&info.FaceName[..len]),
+        }
+    }
+}
+
+impl<'a> From<&'a FontInfo> for CONSOLE_FONT_INFOEX {
+    /// The face name is truncated to fit into the `LF_FACESIZE` (32) UTF-16 code units of `FaceName`,
+    /// including its terminating null, the rest of the array is padded with nulls.
+    fn from(info: &'a FontInfo) -> Self {
+        let mut face_name = [0; LF_FACESIZE];
+        for (dest, unit) in face_name[..LF_FACESIZE - 1]
+            .iter_mut()
+            .zip(info.face_name.encode_utf16())
+        {
+            *dest = unit;
+        }
+
+        CONSOLE_FONT_INFOEX {
+            cbSize: size_of::<CONSOLE_FONT_INFOEX>() as u32,
+            nFont: info.index,
+            dwFontSize: COORD {
+                X: info.size.width,
+                Y: info.size.height,
+            },
+            FontFamily: info.family,
+            FontWeight: info.weight,
+            FaceName: face_name,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use winapi::um::wincon::CONSOLE_FONT_INFOEX;
+
+    use super::FontInfo;
+    use crate::Size;
+
+    #[test]
+    fn face_name_round_trip() {
+        let info = FontInfo {
+            index: 3,
+            size: Size::new(8, 16),
+            family: 54,
+            weight: 400,
+            face_name: String::from("Consolas"),
+        };
+
+        let raw = CONSOLE_FONT_INFOEX::from(&info);
+        assert_eq!(raw.FaceName[8], 0);
+        assert_eq!(FontInfo::from(raw), info);
+    }
+
+    #[test]
+    fn long_face_name_is_truncated() {
+        let info = FontInfo {
+            face_name: "x".repeat(40),
+            ..FontInfo::default()
+        };
+
+        let raw = CONSOLE_FONT_INFOEX::from(&info);
+        assert_eq!(raw.FaceName[31], 0);
+        assert_eq!(FontInfo::from(raw).face_name, "x".repeat(31));
+    }
+}
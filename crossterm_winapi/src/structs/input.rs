@@ -8,17 +8,32 @@
 //! - `EventFlags`
 //! - `InputEventType`
 //! - `INPUT_RECORD`
+//! - `Event`
 
-use winapi::shared::minwindef::{DWORD, WORD};
+use std::char;
+use std::fmt;
+use std::io::Result;
+use std::mem::zeroed;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use winapi::shared::minwindef::{BOOL, DWORD, WORD};
 use winapi::um::wincon::{
-    INPUT_RECORD_Event, KEY_EVENT_RECORD_uChar, FOCUS_EVENT, INPUT_RECORD, KEY_EVENT,
-    KEY_EVENT_RECORD, MENU_EVENT, MOUSE_EVENT, MOUSE_EVENT_RECORD, WINDOW_BUFFER_SIZE_EVENT,
+    INPUT_RECORD_Event, CAPSLOCK_ON, COORD, ENHANCED_KEY, FOCUS_EVENT, INPUT_RECORD, KEY_EVENT,
+    KEY_EVENT_RECORD, LEFT_ALT_PRESSED, LEFT_CTRL_PRESSED, MENU_EVENT, MOUSE_EVENT,
+    MOUSE_EVENT_RECORD, NUMLOCK_ON, RIGHT_ALT_PRESSED, RIGHT_CTRL_PRESSED, SCROLLLOCK_ON,
+    SHIFT_PRESSED, WINDOW_BUFFER_SIZE_EVENT,
 };
 
 use super::coord::Coord;
+use super::WindowPositions;
+use crate::{Console, ConsoleError};
 
 /// Describes a keyboard input event in a console INPUT_RECORD structure.
 /// link: [https://docs.microsoft.com/en-us/windows/console/key-event-record-str]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct KeyEventRecord {
     /// If the key is pressed, this member is TRUE. Otherwise, this member is FALSE (the key is released).
     pub key_down: bool,
@@ -29,20 +44,26 @@ pub struct KeyEventRecord {
     pub virtual_key_code: WORD,
     /// The virtual scan code of the given key that represents the device-dependent value generated by the keyboard hardware.
     pub virtual_scan_code: u16,
-    /// A union of the following members.
+    /// The translated Unicode character.
     ///
-    /// - UnicodeChar
-    ///   Translated Unicode character.
-    ///
-    /// - AsciiChar
-    ///  Translated ASCII character.
-    /// TODO, make this a rust type.
-    pub u_char: KEY_EVENT_RECORD_uChar,
+    /// Characters outside of the basic multilingual plane are reported as two separate key events, one for each surrogate.
+    /// Those surrogates are not valid characters on their own and are reported as `U+FFFD`.
+    pub u_char: char,
     /// The state of the control keys.
     pub control_key_state: ControlKeyState,
 }
 
-impl KeyEventRecord {}
+impl KeyEventRecord {
+    /// Returns whether this event reports the key being pressed, this is also the case for repeats when the key is held down.
+    pub fn is_pressed(&self) -> bool {
+        self.key_down
+    }
+
+    /// Returns whether this event reports the key being released.
+    pub fn is_released(&self) -> bool {
+        !self.key_down
+    }
+}
 
 impl From<KEY_EVENT_RECORD> for KeyEventRecord {
     fn from(event: KEY_EVENT_RECORD) -> Self {
@@ -51,31 +72,135 @@ impl From<KEY_EVENT_RECORD> for KeyEventRecord {
             repeat_count: event.wRepeatCount,
             virtual_key_code: event.wVirtualKeyCode,
             virtual_scan_code: event.wVirtualScanCode,
-            u_char: event.uChar,
+            u_char: char::from_u32(u32::from(unsafe { *event.uChar.UnicodeChar() }))
+                .unwrap_or(char::REPLACEMENT_CHARACTER),
             control_key_state: ControlKeyState(event.dwControlKeyState),
         }
     }
 }
 
-#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+impl From<KeyEventRecord> for KEY_EVENT_RECORD {
+    /// Characters outside of the basic multilingual plane don't fit in a single record and are written as `U+FFFD`.
+    fn from(event: KeyEventRecord) -> Self {
+        let mut raw: KEY_EVENT_RECORD = unsafe { zeroed() };
+        raw.bKeyDown = event.key_down as BOOL;
+        raw.wRepeatCount = event.repeat_count;
+        raw.wVirtualKeyCode = event.virtual_key_code;
+        raw.wVirtualScanCode = event.virtual_scan_code;
+        unsafe {
+            *raw.uChar.UnicodeChar_mut() = if (event.u_char as u32) <= 0xFFFF {
+                event.u_char as u16
+            } else {
+                char::REPLACEMENT_CHARACTER as u16
+            };
+        }
+        raw.dwControlKeyState = event.control_key_state.state();
+        raw
+    }
+}
+
+/// Describes a mouse input event in a console INPUT_RECORD structure.
+/// link: [https://docs.microsoft.com/en-us/windows/console/mouse-event-record-str]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
 pub struct MouseEvent {
     pub mouse_position: Coord,
     pub button_state: ButtonState,
+    /// The unparsed `dwButtonState`, for wheel events the high word contains the signed wheel delta.
+    pub raw_button_state: u32,
     pub control_key_state: ControlKeyState,
     pub event_flags: EventFlags,
 }
 
+impl MouseEvent {
+    /// Get the position of the mouse, in cells of the screen buffer.
+    ///
+    /// When the window is scrolled, this is not the position inside of the visible window, see `position_in_window`.
+    pub fn position(&self) -> Coord {
+        self.mouse_position
+    }
+
+    /// Get the position of the mouse relative to the top left cell of the given window,
+    /// or `None` if the mouse is outside of it.
+    pub fn position_in(&self, window: WindowPositions) -> Option<Coord> {
+        if window.contains(self.mouse_position) {
+            Some(Coord::new(
+                self.mouse_position.x - window.left,
+                self.mouse_position.y - window.top,
+            ))
+        } else {
+            None
+        }
+    }
+
+    /// Get the position of the mouse relative to the top left cell of the visible window of the given console.
+    ///
+    /// This returns an error of kind `InvalidInput` if the mouse is outside of the visible window.
+    pub fn position_in_window(&self, console: &Console) -> Result<Coord> {
+        let window = console.screen_buffer_info()?.terminal_window();
+
+        self.position_in(window)
+            .ok_or_else(|| ConsoleError::OutsideWindow(self.mouse_position).into())
+    }
+
+    /// Decodes what kind of mouse event this is from the event flags and button state.
+    pub fn kind(&self) -> MouseKind {
+        match self.event_flags {
+            EventFlags::PressOrRelease if self.raw_button_state & 0xFFFF != 0 => MouseKind::Press,
+            EventFlags::PressOrRelease => MouseKind::Release,
+            EventFlags::MouseMoved => MouseKind::Move,
+            EventFlags::DoubleClick => MouseKind::DoubleClick,
+            EventFlags::MouseWheeled => MouseKind::WheelVertical(self.wheel_delta()),
+            EventFlags::MouseHwheeled => MouseKind::WheelHorizontal(self.wheel_delta()),
+        }
+    }
+
+    /// The wheel delta is stored as a signed value in the high word of the button state.
+    fn wheel_delta(&self) -> i16 {
+        (self.raw_button_state >> 16) as u16 as i16
+    }
+}
+
 impl From<MOUSE_EVENT_RECORD> for MouseEvent {
     fn from(event: MOUSE_EVENT_RECORD) -> Self {
         MouseEvent {
             mouse_position: Coord::from(event.dwMousePosition),
             button_state: ButtonState::from(event.dwButtonState),
+            raw_button_state: event.dwButtonState,
             control_key_state: ControlKeyState(event.dwControlKeyState),
             event_flags: EventFlags::from(event.dwEventFlags),
         }
     }
 }
 
+impl From<MouseEvent> for MOUSE_EVENT_RECORD {
+    fn from(event: MouseEvent) -> Self {
+        MOUSE_EVENT_RECORD {
+            dwMousePosition: COORD::from(event.mouse_position),
+            dwButtonState: event.raw_button_state,
+            dwControlKeyState: event.control_key_state.state(),
+            dwEventFlags: event.event_flags as DWORD,
+        }
+    }
+}
+
+/// The kind of a mouse event, see `MouseEvent::kind`.
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
+pub enum MouseKind {
+    /// One or more mouse buttons were pressed.
+    Press,
+    /// All mouse buttons were released.
+    Release,
+    /// The mouse moved.
+    Move,
+    /// The second click of a double-click occurred, the first click is reported as a regular press.
+    DoubleClick,
+    /// The vertical wheel was rotated, a positive delta means forward (away from the user) and a negative delta backward.
+    WheelVertical(i16),
+    /// The horizontal wheel was rotated, a positive delta means to the right and a negative delta to the left.
+    WheelHorizontal(i16),
+}
+
 /// The status of the mouse buttons.
 /// The least significant bit corresponds to the leftmost mouse button.
 /// The next least significant bit corresponds to the rightmost mouse button.
@@ -84,7 +209,8 @@ impl From<MOUSE_EVENT_RECORD> for MouseEvent {
 /// A bit is 1 if the button was pressed.
 ///
 /// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/mouse-event-record-str#members)
-#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum ButtonState {
     Release = 0x0000,
     /// The leftmost mouse button.
@@ -120,13 +246,63 @@ impl From<DWORD> for ButtonState {
     }
 }
 
-#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+/// The state of the control keys, like shift, ctrl, alt and the lock keys.
+///
+/// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/key-event-record-str#members)
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
 pub struct ControlKeyState(u32);
 
 impl ControlKeyState {
+    /// Create a new control key state from the raw `dwControlKeyState` flags.
+    pub fn new(state: u32) -> ControlKeyState {
+        ControlKeyState(state)
+    }
+
+    /// Returns the raw `dwControlKeyState` flags.
+    pub fn state(&self) -> u32 {
+        self.0
+    }
+
+    /// Checks if any of the given flags are set.
     pub fn has_state(&self, state: u32) -> bool {
         (state & self.0) != 0
     }
+
+    /// Checks if the shift key is pressed.
+    pub fn shift(&self) -> bool {
+        self.has_state(SHIFT_PRESSED)
+    }
+
+    /// Checks if the left or right ctrl key is pressed.
+    pub fn ctrl(&self) -> bool {
+        self.has_state(LEFT_CTRL_PRESSED | RIGHT_CTRL_PRESSED)
+    }
+
+    /// Checks if the left or right alt key is pressed.
+    pub fn alt(&self) -> bool {
+        self.has_state(LEFT_ALT_PRESSED | RIGHT_ALT_PRESSED)
+    }
+
+    /// Checks if the caps lock light is on.
+    pub fn caps_lock(&self) -> bool {
+        self.has_state(CAPSLOCK_ON)
+    }
+
+    /// Checks if the num lock light is on.
+    pub fn num_lock(&self) -> bool {
+        self.has_state(NUMLOCK_ON)
+    }
+
+    /// Checks if the scroll lock light is on.
+    pub fn scroll_lock(&self) -> bool {
+        self.has_state(SCROLLLOCK_ON)
+    }
+
+    /// Checks if the key is enhanced, like the arrow keys and the keys on the numeric keypad.
+    pub fn enhanced_key(&self) -> bool {
+        self.has_state(ENHANCED_KEY)
+    }
 }
 
 /// The type of mouse event.
@@ -134,7 +310,8 @@ impl ControlKeyState {
 /// Otherwise, this member is one of the following values.
 ///
 /// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/mouse-event-record-str#members)
-#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum EventFlags {
     PressOrRelease = 0x0000,
     // The second click (button press) of a double-click occurred. The first click is returned as a regular button-press event.
@@ -165,8 +342,15 @@ impl From<DWORD> for EventFlags {
 /// Describes an input event in the console input buffer.
 /// These records can be read from the input buffer by using the `ReadConsoleInput` or `PeekConsoleInput` function, or written to the input buffer by using the `WriteConsoleInput` function.
 ///
+/// The raw event information is a union, so comparing and debug formatting is done on the decoded `event()`.
+///
 /// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/input-record-str)
-#[derive(Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(Serialize, Deserialize),
+    serde(from = "Event", into = "Event")
+)]
+#[derive(Copy, Clone)]
 pub struct InputRecord {
     /// A handle to the type of input event and the event record stored in the Event member.
     pub event_type: InputEventType,
@@ -175,6 +359,62 @@ pub struct InputRecord {
     pub event: INPUT_RECORD_Event,
 }
 
+impl InputRecord {
+    /// Decodes the event information of this record into an `Event` according to its event type.
+    pub fn event(&self) -> Event {
+        unsafe {
+            match self.event_type {
+                InputEventType::KeyEvent => {
+                    Event::Key(KeyEventRecord::from(*self.event.KeyEvent()))
+                }
+                InputEventType::MouseEvent => {
+                    Event::Mouse(MouseEvent::from(*self.event.MouseEvent()))
+                }
+                InputEventType::WindowBufferSizeEvent => {
+                    Event::WindowBufferSize(Coord::from(self.event.WindowBufferSizeEvent().dwSize))
+                }
+                InputEventType::FocusEvent => Event::Focus(self.event.FocusEvent().bSetFocus != 0),
+                InputEventType::MenuEvent => Event::Menu(self.event.MenuEvent().dwCommandId),
+            }
+        }
+    }
+
+    /// Returns the key event of this record, or `None` if this record describes another kind of event.
+    ///
+    /// This is handy for dropping all other events, like `records.iter().filter_map(InputRecord::as_key)`.
+    pub fn as_key(&self) -> Option<KeyEventRecord> {
+        match self.event() {
+            Event::Key(key_event) => Some(key_event),
+            _ => None,
+        }
+    }
+
+    /// Returns the mouse event of this record, or `None` if this record describes another kind of event.
+    pub fn as_mouse(&self) -> Option<MouseEvent> {
+        match self.event() {
+            Event::Mouse(mouse_event) => Some(mouse_event),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Debug for InputRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("InputRecord")
+            .field("event_type", &self.event_type)
+            .field("event", &self.event())
+            .finish()
+    }
+}
+
+impl PartialEq for InputRecord {
+    fn eq(&self, other: &InputRecord) -> bool {
+        self.event_type == other.event_type && self.event() == other.event()
+    }
+}
+
+impl Eq for InputRecord {}
+
 impl From<INPUT_RECORD> for InputRecord {
     fn from(event: INPUT_RECORD) -> Self {
         InputRecord {
@@ -184,10 +424,86 @@ impl From<INPUT_RECORD> for InputRecord {
     }
 }
 
+impl From<InputRecord> for INPUT_RECORD {
+    fn from(record: InputRecord) -> Self {
+        INPUT_RECORD {
+            EventType: record.event_type as WORD,
+            Event: record.event,
+        }
+    }
+}
+
+/// The decoded event information of an `InputRecord`.
+///
+/// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/input-record-str#members)
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Event {
+    /// A keyboard event.
+    Key(KeyEventRecord),
+    /// A mouse movement or button press event.
+    Mouse(MouseEvent),
+    /// The screen buffer was resized, this contains the new size of the screen buffer.
+    WindowBufferSize(Coord),
+    /// The console gained (`true`) or lost (`false`) focus. These events are used internally and should be ignored.
+    Focus(bool),
+    /// A menu command with the given command id. These events are used internally and should be ignored.
+    Menu(u32),
+}
+
+impl From<InputRecord> for Event {
+    fn from(record: InputRecord) -> Self {
+        record.event()
+    }
+}
+
+impl From<Event> for InputRecord {
+    fn from(event: Event) -> Self {
+        let mut raw: INPUT_RECORD = unsafe { zeroed() };
+
+        unsafe {
+            match event {
+                Event::Key(key_event) => {
+                    raw.EventType = KEY_EVENT;
+                    *raw.Event.KeyEvent_mut() = KEY_EVENT_RECORD::from(key_event);
+                }
+                Event::Mouse(mouse_event) => {
+                    raw.EventType = MOUSE_EVENT;
+                    *raw.Event.MouseEvent_mut() = MOUSE_EVENT_RECORD::from(mouse_event);
+                }
+                Event::WindowBufferSize(size) => {
+                    raw.EventType = WINDOW_BUFFER_SIZE_EVENT;
+                    raw.Event.WindowBufferSizeEvent_mut().dwSize = COORD::from(size);
+                }
+                Event::Focus(set_focus) => {
+                    raw.EventType = FOCUS_EVENT;
+                    raw.Event.FocusEvent_mut().bSetFocus = set_focus as BOOL;
+                }
+                Event::Menu(command_id) => {
+                    raw.EventType = MENU_EVENT;
+                    raw.Event.MenuEvent_mut().dwCommandId = command_id;
+                }
+            }
+        }
+
+        InputRecord::from(raw)
+    }
+}
+
+/// The new size of the console after a resize, see `Console::resize_event`.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct ResizeEvent {
+    /// the new size of the screen buffer, as reported by the `WINDOW_BUFFER_SIZE_EVENT`
+    pub buffer: Coord,
+    /// the size of the visible window, `x` being the width and `y` the height
+    pub window: Coord,
+}
+
 /// A handle to the type of input event and the event record stored in the Event member.
 ///
 /// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/input-record-str#members)
-#[derive(PartialOrd, PartialEq, Debug, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
 pub enum InputEventType {
     /// The Event member contains a `KEY_EVENT_RECORD` structure with information about a keyboard event.
     KeyEvent = KEY_EVENT as isize,
@@ -213,3 +529,219 @@ impl From<WORD> for InputEventType {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use std::mem::zeroed;
+
+    use winapi::um::wincon::{
+        CAPSLOCK_ON, DOUBLE_CLICK, FROM_LEFT_1ST_BUTTON_PRESSED, INPUT_RECORD, KEY_EVENT,
+        LEFT_CTRL_PRESSED, MOUSE_EVENT_RECORD, MOUSE_HWHEELED, MOUSE_MOVED, MOUSE_WHEELED,
+        RIGHT_ALT_PRESSED, SHIFT_PRESSED,
+    };
+
+    use super::{ControlKeyState, Event, InputRecord, MouseEvent, MouseKind};
+    use crate::{Coord, WindowPositions};
+
+    fn key_record(unicode_char: u16) -> InputRecord {
+        let mut raw: INPUT_RECORD = unsafe { zeroed() };
+        raw.EventType = KEY_EVENT;
+        unsafe {
+            let key_event = raw.Event.KeyEvent_mut();
+            key_event.bKeyDown = 1;
+            *key_event.uChar.UnicodeChar_mut() = unicode_char;
+        }
+        InputRecord::from(raw)
+    }
+
+    #[test]
+    fn event_round_trip() {
+        let key = key_record(0x00E9);
+        assert_eq!(InputRecord::from(key.event()), key);
+
+        let mouse = Event::Mouse(mouse_event(0xFF88_0000, MOUSE_WHEELED));
+        assert_eq!(InputRecord::from(mouse).event(), mouse);
+
+        assert_eq!(
+            InputRecord::from(Event::Focus(true)).event(),
+            Event::Focus(true)
+        );
+        assert_eq!(InputRecord::from(Event::Menu(7)).event(), Event::Menu(7));
+    }
+
+    #[test]
+    fn compare_and_debug_records() {
+        let record = key_record(u16::from(b'a'));
+        let copy = record;
+
+        assert_eq!(record, copy);
+        assert_ne!(record, key_record(u16::from(b'b')));
+        assert!(format!("{:?}", record).contains("u_char: 'a'"));
+
+        match record.event() {
+            Event::Key(key_event) => assert!(key_event.key_down),
+            event => panic!("expected a key event, got {:?}", event),
+        }
+    }
+
+    // the serialized form is what recorded input is stored as, so changing it breaks existing recordings.
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialized_form_is_stable() {
+        use serde_test::{assert_tokens, Token};
+
+        assert_tokens(
+            &key_record(u16::from(b'a')),
+            &[
+                Token::NewtypeVariant {
+                    name: "Event",
+                    variant: "Key",
+                },
+                Token::Struct {
+                    name: "KeyEventRecord",
+                    len: 6,
+                },
+                Token::Str("key_down"),
+                Token::Bool(true),
+                Token::Str("repeat_count"),
+                Token::U16(0),
+                Token::Str("virtual_key_code"),
+                Token::U16(0),
+                Token::Str("virtual_scan_code"),
+                Token::U16(0),
+                Token::Str("u_char"),
+                Token::Char('a'),
+                Token::Str("control_key_state"),
+                Token::NewtypeStruct {
+                    name: "ControlKeyState",
+                },
+                Token::U32(0),
+                Token::StructEnd,
+            ],
+        );
+
+        assert_tokens(
+            &Coord::new(3, -4),
+            &[
+                Token::Struct {
+                    name: "Coord",
+                    len: 2,
+                },
+                Token::Str("x"),
+                Token::I16(3),
+                Token::Str("y"),
+                Token::I16(-4),
+                Token::StructEnd,
+            ],
+        );
+
+        assert_tokens(
+            &WindowPositions {
+                left: 1,
+                right: 80,
+                bottom: 25,
+                top: 2,
+            },
+            &[
+                Token::Struct {
+                    name: "WindowPositions",
+                    len: 4,
+                },
+                Token::Str("left"),
+                Token::I16(1),
+                Token::Str("right"),
+                Token::I16(80),
+                Token::Str("bottom"),
+                Token::I16(25),
+                Token::Str("top"),
+                Token::I16(2),
+                Token::StructEnd,
+            ],
+        );
+    }
+
+    #[test]
+    fn typed_accessors() {
+        let key = key_record(u16::from(b'q'));
+        assert_eq!(key.as_key().map(|key_event| key_event.u_char), Some('q'));
+        assert_eq!(key.as_mouse(), None);
+
+        let mouse = mouse_event(0, MOUSE_MOVED);
+        let record = InputRecord::from(Event::Mouse(mouse));
+        assert_eq!(record.as_mouse(), Some(mouse));
+        assert_eq!(record.as_key(), None);
+
+        let records = [key, record, InputRecord::from(Event::Focus(true))];
+        assert_eq!(records.iter().filter_map(InputRecord::as_key).count(), 1);
+    }
+
+    fn mouse_event(button_state: u32, event_flags: u32) -> MouseEvent {
+        let mut event: MOUSE_EVENT_RECORD = unsafe { zeroed() };
+        event.dwButtonState = button_state;
+        event.dwEventFlags = event_flags;
+        MouseEvent::from(event)
+    }
+
+    #[test]
+    fn mouse_kind() {
+        assert_eq!(
+            mouse_event(FROM_LEFT_1ST_BUTTON_PRESSED, 0).kind(),
+            MouseKind::Press
+        );
+        assert_eq!(mouse_event(0, 0).kind(), MouseKind::Release);
+        assert_eq!(mouse_event(0, MOUSE_MOVED).kind(), MouseKind::Move);
+        assert_eq!(
+            mouse_event(FROM_LEFT_1ST_BUTTON_PRESSED, DOUBLE_CLICK).kind(),
+            MouseKind::DoubleClick
+        );
+    }
+
+    #[test]
+    fn mouse_position_in_window() {
+        let window = WindowPositions {
+            left: 10,
+            right: 89,
+            bottom: 124,
+            top: 100,
+        };
+
+        let mut event = mouse_event(FROM_LEFT_1ST_BUTTON_PRESSED, 0);
+        event.mouse_position = Coord::new(15, 102);
+        assert_eq!(event.position(), Coord::new(15, 102));
+        assert_eq!(event.position_in(window), Some(Coord::new(5, 2)));
+
+        event.mouse_position = Coord::new(15, 99);
+        assert_eq!(event.position_in(window), None);
+    }
+
+    #[test]
+    fn mouse_wheel_delta() {
+        assert_eq!(
+            mouse_event(120 << 16, MOUSE_WHEELED).kind(),
+            MouseKind::WheelVertical(120)
+        );
+        // scrolling down is a negative delta of -120, stored as 0xFF88 in the high word.
+        assert_eq!(
+            mouse_event(0xFF88_0000, MOUSE_WHEELED).kind(),
+            MouseKind::WheelVertical(-120)
+        );
+        assert_eq!(
+            mouse_event(0xFF88_0000, MOUSE_HWHEELED).kind(),
+            MouseKind::WheelHorizontal(-120)
+        );
+    }
+
+    #[test]
+    fn control_key_state() {
+        let state = ControlKeyState::new(SHIFT_PRESSED | RIGHT_ALT_PRESSED | CAPSLOCK_ON);
+        assert!(state.shift());
+        assert!(state.alt());
+        assert!(state.caps_lock());
+        assert!(!state.ctrl());
+        assert!(!state.num_lock());
+
+        let state = ControlKeyState::new(LEFT_CTRL_PRESSED);
+        assert!(state.ctrl());
+        assert!(!state.shift());
+    }
+}
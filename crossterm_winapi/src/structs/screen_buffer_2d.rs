@@ -0,0 +1,151 @@
+//! This module provides a grid view of the cells that were read from a screen buffer.
+//! For example, `Console::read_output` returns the cells as a flat `Vec<CharInfo>` stored row by row.
+//! This module provides a type that indexes those cells by column and row instead.
+
+use std::io;
+
+use super::{CharInfo, Coord};
+use crate::ConsoleError;
+
+/// The cells of a rectangle of the screen buffer, stored row by row, that can be indexed by column and row.
+///
+/// ```no_run
+/// # use crossterm_winapi::{Console, Coord, ScreenBuffer2D, WindowPositions};
+/// # fn main() -> std::io::Result<()> {
+/// let console = Console::new()?;
+/// let size = Coord::new(10, 2);
+/// let region = WindowPositions { left: 0, right: 9, top: 0, bottom: 1 };
+///
+/// let (cells, _) = console.read_output(size, Coord::ORIGIN, region)?;
+/// let grid = ScreenBuffer2D::new(cells, size)?;
+///
+/// if let Some(cell) = grid.get(3, 1) {
+///     println!("the fourth cell of the second row holds {:?}", cell.ch);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScreenBuffer2D {
+    cells: Vec<CharInfo>,
+    width: usize,
+    height: usize,
+}
+
+impl ScreenBuffer2D {
+    /// Create a grid from cells stored row by row, `size` being the width and height like the buffer size of `Console::read_output`.
+    ///
+    /// Fails with an error of kind `InvalidInput` if `size` has a negative axis or the number of cells doesn't match it.
+    pub fn new(cells: Vec<CharInfo>, size: Coord) -> io::Result<ScreenBuffer2D> {
+        if size.x < 0 || size.y < 0 {
+            return Err(ConsoleError::InvalidSize(size).into());
+        }
+
+        let (width, height) = (size.x as usize, size.y as usize);
+
+        if cells.len() != width * height {
+            return Err(ConsoleError::BufferLength {
+                len: cells.len(),
+                size,
+            }
+            .into());
+        }
+
+        Ok(ScreenBuffer2D {
+            cells,
+            width,
+            height,
+        })
+    }
+
+    /// Get the number of columns.
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Get the number of rows.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Get the cell in column `x` of row `y`, or `None` if that lies outside of the grid.
+    pub fn get(&self, x: usize, y: usize) -> Option<&CharInfo> {
+        if x < self.width && y < self.height {
+            self.cells.get(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    /// Get the cells of row `y`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `y` is not smaller than the height of the grid.
+    pub fn row(&self, y: usize) -> &[CharInfo] {
+        assert!(
+            y < self.height,
+            "row {} is outside of a grid with {} rows",
+            y,
+            self.height
+        );
+
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Get an iterator over the rows of the grid, from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[CharInfo]> {
+        // `chunks` panics on a chunk size of 0, a grid without columns has no cells to iterate anyway.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Get all cells, stored row by row.
+    pub fn cells(&self) -> &[CharInfo] {
+        &self.cells
+    }
+
+    /// Returns the cells, stored row by row.
+    pub fn into_cells(self) -> Vec<CharInfo> {
+        self.cells
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ScreenBuffer2D;
+    use crate::{CharInfo, Coord};
+
+    fn grid() -> ScreenBuffer2D {
+        let cells = "abcdef".chars().map(|ch| CharInfo::new(ch, 0)).collect();
+        ScreenBuffer2D::new(cells, Coord::new(3, 2)).unwrap()
+    }
+
+    #[test]
+    fn index_cells() {
+        let grid = grid();
+
+        assert_eq!(grid.get(0, 0).map(|cell| cell.ch), Some('a'));
+        assert_eq!(grid.get(2, 1).map(|cell| cell.ch), Some('f'));
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.get(0, 2), None);
+
+        let row: String = grid.row(1).iter().map(|cell| cell.ch).collect();
+        assert_eq!(row, "def");
+        assert_eq!(grid.rows().count(), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn row_out_of_bounds() {
+        grid().row(2);
+    }
+
+    #[test]
+    fn size_must_match_cells() {
+        let cells = vec![CharInfo::new(' ', 0); 5];
+
+        assert!(ScreenBuffer2D::new(cells.clone(), Coord::new(3, 2)).is_err());
+        assert!(ScreenBuffer2D::new(cells, Coord::new(-5, -1)).is_err());
+        assert!(ScreenBuffer2D::new(Vec::new(), Coord::new(0, 4)).is_ok());
+    }
+}
@@ -0,0 +1,116 @@
+//! This module provides a type that represents the text selection of the console.
+//! For example, in WinAPi we have `CONSOLE_SELECTION_INFO` which stores the selection flags as a raw `DWORD`.
+//! This module provides some trait implementations who will make parsing and working whit `CONSOLE_SELECTION_INFO` easier.
+
+use winapi::um::wincon::{
+    CONSOLE_MOUSE_DOWN, CONSOLE_MOUSE_SELECTION, CONSOLE_SELECTION_INFO,
+    CONSOLE_SELECTION_IN_PROGRESS, CONSOLE_SELECTION_NOT_EMPTY,
+};
+
+use super::{Coord, WindowPositions};
+
+/// The state of the console selection, like whether a selection is in progress.
+///
+/// [Ms Docs](https://docs.microsoft.com/en-us/windows/console/console-selection-info-str#members)
+#[derive(PartialOrd, PartialEq, Eq, Debug, Copy, Clone)]
+pub struct SelectionFlags(u32);
+
+impl SelectionFlags {
+    /// Create new selection flags from the raw `dwFlags`.
+    pub fn new(flags: u32) -> SelectionFlags {
+        SelectionFlags(flags)
+    }
+
+    /// Returns the raw `dwFlags`.
+    pub fn flags(&self) -> u32 {
+        self.0
+    }
+
+    /// Checks if a selection is in progress.
+    pub fn in_progress(&self) -> bool {
+        self.0 & CONSOLE_SELECTION_IN_PROGRESS != 0
+    }
+
+    /// Checks if the selection rectangle is not empty.
+    pub fn not_empty(&self) -> bool {
+        self.0 & CONSOLE_SELECTION_NOT_EMPTY != 0
+    }
+
+    /// Checks if the selection is made with the mouse.
+    pub fn mouse_selection(&self) -> bool {
+        self.0 & CONSOLE_MOUSE_SELECTION != 0
+    }
+
+    /// Checks if the mouse button is down while selecting.
+    pub fn mouse_down(&self) -> bool {
+        self.0 & CONSOLE_MOUSE_DOWN != 0
+    }
+}
+
+/// This is a wrapper for the selection of the console.
+#[derive(PartialEq, Eq, Debug, Copy, Clone)]
+pub struct SelectionInfo {
+    /// the state of the selection
+    pub flags: SelectionFlags,
+    /// the cell where the selection started
+    pub anchor: Coord,
+    /// the selected rectangle, edges included
+    pub selection: WindowPositions,
+}
+
+impl From<CONSOLE_SELECTION_INFO> for SelectionInfo {
+    fn from(info: CONSOLE_SELECTION_INFO) -> Self {
+        SelectionInfo {
+            flags: SelectionFlags(info.dwFlags),
+            anchor: Coord::from(info.dwSelectionAnchor),
+            selection: WindowPositions::from(info.srSelection),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::mem::zeroed;
+
+    use winapi::um::wincon::CONSOLE_SELECTION_INFO;
+
+    use super::{SelectionFlags, SelectionInfo};
+    use crate::{Coord, WindowPositions};
+
+    #[test]
+    fn selection_flags() {
+        let flags = SelectionFlags::new(0x1 | 0x4);
+
+        assert!(flags.in_progress());
+        assert!(!flags.not_empty());
+        assert!(flags.mouse_selection());
+        assert!(!flags.mouse_down());
+        assert_eq!(flags.flags(), 0x5);
+    }
+
+    #[test]
+    fn from_raw_selection_info() {
+        let mut raw: CONSOLE_SELECTION_INFO = unsafe { zeroed() };
+        raw.dwFlags = 0x2;
+        raw.dwSelectionAnchor.X = 3;
+        raw.dwSelectionAnchor.Y = 1;
+        raw.srSelection.Left = 3;
+        raw.srSelection.Top = 1;
+        raw.srSelection.Right = 7;
+        raw.srSelection.Bottom = 2;
+
+        let info = SelectionInfo::from(raw);
+
+        assert!(info.flags.not_empty());
+        assert_eq!(info.anchor, Coord::new(3, 1));
+        assert_eq!(
+            info.selection,
+            WindowPositions {
+                left: 3,
+                right: 7,
+                bottom: 2,
+                top: 1,
+            }
+        );
+    }
+}
@@ -2,9 +2,12 @@
 //! For example, in WinAPi we have `COORD` to represent screen/buffer size but this is a little inconvenient.
 //! This module provides some trait implementations who will make parsing and working whit `COORD` easier.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use winapi::um::wincon::COORD;
 
 /// This is type represents the size of something in width and height.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct Size {
     pub width: i16,
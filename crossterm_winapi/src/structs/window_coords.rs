@@ -2,11 +2,16 @@
 //! For example, in WinAPi we have `SMALL_RECT` to represent a window size but this is a little inconvenient.
 //! This module provides some trait implementations who will make parsing and working whit `COORD` easier.
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 use winapi::um::wincon::{CONSOLE_SCREEN_BUFFER_INFO, SMALL_RECT};
 
+use super::Coord;
+
 /// This is a wrapper for the locations of a rectangle.
 ///
 /// It has left, right, bottom, top attributes.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
 pub struct WindowPositions {
     pub left: i16,
@@ -15,6 +20,23 @@ pub struct WindowPositions {
     pub top: i16,
 }
 
+impl WindowPositions {
+    /// Get the width of the rectangle, counting both the `left` and `right` column.
+    pub fn width(&self) -> i16 {
+        self.right - self.left + 1
+    }
+
+    /// Get the height of the rectangle, counting both the `top` and `bottom` row.
+    pub fn height(&self) -> i16 {
+        self.bottom - self.top + 1
+    }
+
+    /// Check whether the given coordinate lies inside the rectangle, edges included.
+    pub fn contains(&self, c: Coord) -> bool {
+        c.x >= self.left && c.x <= self.right && c.y >= self.top && c.y <= self.bottom
+    }
+}
+
 impl From<CONSOLE_SCREEN_BUFFER_INFO> for WindowPositions {
     fn from(csbi: CONSOLE_SCREEN_BUFFER_INFO) -> Self {
         WindowPositions {
@@ -26,6 +48,17 @@ impl From<CONSOLE_SCREEN_BUFFER_INFO> for WindowPositions {
     }
 }
 
+impl From<SMALL_RECT> for WindowPositions {
+    fn from(rect: SMALL_RECT) -> Self {
+        WindowPositions {
+            left: rect.Left,
+            right: rect.Right,
+            bottom: rect.Bottom,
+            top: rect.Top,
+        }
+    }
+}
+
 impl From<WindowPositions> for SMALL_RECT {
     fn from(positions: WindowPositions) -> Self {
         SMALL_RECT {
@@ -36,3 +69,26 @@ impl From<WindowPositions> for SMALL_RECT {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::WindowPositions;
+    use crate::Coord;
+
+    #[test]
+    fn dimensions_and_contains() {
+        let rect = WindowPositions {
+            left: 2,
+            right: 11,
+            bottom: 5,
+            top: 1,
+        };
+
+        assert_eq!(rect.width(), 10);
+        assert_eq!(rect.height(), 5);
+        assert!(rect.contains(Coord::new(2, 1)));
+        assert!(rect.contains(Coord::new(11, 5)));
+        assert!(!rect.contains(Coord::new(12, 5)));
+        assert!(!rect.contains(Coord::new(2, 0)));
+    }
+}
@@ -0,0 +1,46 @@
+//! This module contains helpers that are shared by the tests of this crate.
+
+use std::sync::{Mutex, PoisonError};
+
+use super::{Console, Handle, HandleType};
+
+// The tests that use the input buffer of the console share it, so they can not run in parallel.
+static INPUT_BUFFER: Mutex<()> = Mutex::new(());
+
+/// Runs `f` against a new screen buffer that is never shown, so a test doesn't draw on the terminal and starts from a blank buffer.
+///
+/// The buffer is created with `CreateConsoleScreenBuffer` and has the size of the current console window.
+/// It is closed after `f` returns, also when `f` panics.
+pub(crate) fn with_scratch_console(f: impl FnOnce(&Console)) {
+    let console = Console::create_alternate().expect("failed to create a scratch screen buffer");
+    f(&console);
+}
+
+/// Runs `f` against the input buffer of the console running the tests, which starts out empty.
+///
+/// Only one of these tests runs at a time.
+/// The input buffer is flushed before `f` is called and after it returns, also when `f` panics,
+/// so records written by a test don't reach the shell. This discards whatever was typed ahead,
+/// which is why the tests using this helper are ignored and have to be run with `cargo test -- --ignored`.
+pub(crate) fn with_input_console(f: impl FnOnce(&Console)) {
+    struct FlushOnDrop(Console);
+
+    impl Drop for FlushOnDrop {
+        fn drop(&mut self) {
+            let _ = self.0.flush_input();
+        }
+    }
+
+    // a test that failed while holding the lock still flushed the buffer, so a poisoned lock is fine.
+    let _lock = INPUT_BUFFER.lock().unwrap_or_else(PoisonError::into_inner);
+
+    let console = Console::from(
+        Handle::new(HandleType::CurrentInputHandle).expect("failed to open the console input"),
+    );
+    console
+        .flush_input()
+        .expect("failed to flush the console input");
+
+    let console = FlushOnDrop(console);
+    f(&console.0);
+}
@@ -0,0 +1,164 @@
+//! This module provides stand-ins for the most used types of this crate on platforms other than Windows.
+//!
+//! They let code that uses this crate compile everywhere, without gating every `use` with `#[cfg(windows)]`.
+//! A `Handle` or `Console` can't be created on these platforms, every function fails with an error of kind `Unsupported`.
+
+use std::io::{self, Result};
+
+use super::Coord;
+
+/// The error that is returned by every function on platforms other than Windows.
+fn unsupported() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Unsupported,
+        "The WinApi console functions are only available on Windows",
+    )
+}
+
+/// This enum represents the different handles that could be requested.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum HandleType {
+    OutputHandle,
+    InputHandle,
+    CurrentOutputHandle,
+    CurrentInputHandle,
+}
+
+impl HandleType {
+    /// Returns whether this handle type refers to the console input buffer.
+    pub fn is_input(&self) -> bool {
+        match self {
+            HandleType::InputHandle | HandleType::CurrentInputHandle => true,
+            HandleType::OutputHandle | HandleType::CurrentOutputHandle => false,
+        }
+    }
+}
+
+/// A console handle, which can't be created on this platform.
+#[derive(Debug)]
+pub struct Handle {
+    _private: (),
+}
+
+impl Handle {
+    /// Always fails with an error of kind `Unsupported` on this platform.
+    pub fn new(_handle: HandleType) -> Result<Handle> {
+        Err(unsupported())
+    }
+
+    /// There are no console handles on this platform.
+    pub fn is_console(&self) -> bool {
+        false
+    }
+}
+
+/// A console, which can't be created on this platform.
+///
+/// Only the most used functions of the Windows `Console` are provided, they all fail with an error of kind `Unsupported`.
+#[derive(Debug)]
+pub struct Console {
+    _private: (),
+}
+
+impl Console {
+    /// Always fails with an error of kind `Unsupported` on this platform.
+    pub fn new() -> Result<Console> {
+        Err(unsupported())
+    }
+
+    /// Always fails with an error of kind `Unsupported` on this platform.
+    pub fn with_handle_type(_handle_type: HandleType) -> Result<Console> {
+        Err(unsupported())
+    }
+
+    pub fn set_text_attribute(&self, _value: u16) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn current_attributes(&self) -> Result<u16> {
+        Err(unsupported())
+    }
+
+    pub fn set_cursor_position(&self, _pos: Coord) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn cursor_position(&self) -> Result<Coord> {
+        Err(unsupported())
+    }
+
+    pub fn mode(&self) -> Result<u32> {
+        Err(unsupported())
+    }
+
+    pub fn set_mode(&self, _mode: u32) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn clear(&self) -> Result<()> {
+        Err(unsupported())
+    }
+
+    pub fn fill_with_character(
+        &self,
+        _start_location: Coord,
+        _cells_to_write: u32,
+        _filling_char: char,
+    ) -> Result<u32> {
+        Err(unsupported())
+    }
+
+    pub fn fill_with_attribute(
+        &self,
+        _start_location: Coord,
+        _cells_to_write: u32,
+        _dw_attribute: u16,
+    ) -> Result<u32> {
+        Err(unsupported())
+    }
+
+    pub fn write_char_buffer(&self, _buf: &[u8]) -> Result<usize> {
+        Err(unsupported())
+    }
+
+    pub fn write_str(&self, _s: &str) -> Result<usize> {
+        Err(unsupported())
+    }
+
+    pub fn write_utf16(&self, _buf: &[u16]) -> Result<usize> {
+        Err(unsupported())
+    }
+
+    pub fn read_line(&self, _max_chars: u32) -> Result<String> {
+        Err(unsupported())
+    }
+}
+
+impl From<Handle> for Console {
+    fn from(handle: Handle) -> Self {
+        Console {
+            _private: handle._private,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use super::{Console, Handle, HandleType};
+
+    #[test]
+    fn functions_are_unsupported() {
+        assert_eq!(
+            Console::new().unwrap_err().kind(),
+            io::ErrorKind::Unsupported
+        );
+        assert_eq!(
+            Handle::new(HandleType::CurrentOutputHandle)
+                .unwrap_err()
+                .kind(),
+            io::ErrorKind::Unsupported
+        );
+    }
+}
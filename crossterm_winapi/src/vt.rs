@@ -0,0 +1,106 @@
+//! This module tells which virtual terminal sequences the console of the running Windows build understands.
+//!
+//! The console gained support for more sequences with each Windows release,
+//! so the support is derived from the build number that `RtlGetVersion` reports.
+
+use std::mem::{size_of, zeroed};
+
+use winapi::shared::ntdef::NTSTATUS;
+use winapi::um::winnt::RTL_OSVERSIONINFOW;
+
+#[link(name = "ntdll")]
+extern "system" {
+    // `GetVersionEx` reports an older version to applications without a compatibility manifest, this function doesn't.
+    fn RtlGetVersion(lpVersionInformation: *mut RTL_OSVERSIONINFOW) -> NTSTATUS;
+}
+
+/// The first build of Windows 10 version 1511, where the console started to process virtual terminal sequences.
+const VT_BUILD: u32 = 10586;
+/// The first build of Windows 10 version 1703, which supports 24-bit colors in SGR sequences.
+const TRUECOLOR_BUILD: u32 = 15063;
+/// The first build of Windows 10 version 1809, which supports the soft reset sequence `ESC [ ! p`.
+const SOFT_RESET_BUILD: u32 = 17763;
+/// The first build of Windows 10 version 1903, which reports mouse input in the SGR encoding.
+const MOUSE_SGR_BUILD: u32 = 18362;
+/// The first build of Windows 11 version 22H2, which supports bracketed paste.
+const BRACKETED_PASTE_BUILD: u32 = 22621;
+
+/// The virtual terminal sequences that can be used safely, see `Console::vt_capabilities`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct VtCaps {
+    /// 24-bit colors in SGR sequences, like `ESC [ 38 ; 2 ; r ; g ; b m`.
+    pub truecolor: bool,
+    /// Mouse input reported in the SGR encoding, enabled with `ESC [ ? 1006 h`.
+    pub mouse_sgr: bool,
+    /// Pasted text wrapped in markers, enabled with `ESC [ ? 2004 h`.
+    pub bracketed_paste: bool,
+    /// The soft reset sequence `ESC [ ! p`.
+    pub soft_reset: bool,
+}
+
+impl VtCaps {
+    /// Get the capabilities of the console on the given Windows build number.
+    ///
+    /// Builds before Windows 10 version 1511 don't process virtual terminal sequences at all, so nothing is supported there.
+    pub fn for_build(build: u32) -> VtCaps {
+        if build < VT_BUILD {
+            return VtCaps::default();
+        }
+
+        VtCaps {
+            truecolor: build >= TRUECOLOR_BUILD,
+            mouse_sgr: build >= MOUSE_SGR_BUILD,
+            bracketed_paste: build >= BRACKETED_PASTE_BUILD,
+            soft_reset: build >= SOFT_RESET_BUILD,
+        }
+    }
+}
+
+/// Get the build number of the running Windows version, or `None` if it couldn't be retrieved.
+///
+/// Wraps the underlying function call: [RtlGetVersion]
+/// link: [https://docs.microsoft.com/en-us/windows-hardware/drivers/ddi/wdm/nf-wdm-rtlgetversion]
+pub(crate) fn build_number() -> Option<u32> {
+    let mut info: RTL_OSVERSIONINFOW = unsafe { zeroed() };
+    info.dwOSVersionInfoSize = size_of::<RTL_OSVERSIONINFOW>() as u32;
+
+    // `RtlGetVersion` returns `STATUS_SUCCESS`, which is 0, when it succeeds.
+    if unsafe { RtlGetVersion(&mut info) } == 0 {
+        Some(info.dwBuildNumber)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{build_number, VtCaps};
+
+    #[test]
+    fn capabilities_per_build() {
+        assert_eq!(VtCaps::for_build(9600), VtCaps::default());
+        assert_eq!(
+            VtCaps::for_build(17134),
+            VtCaps {
+                truecolor: true,
+                mouse_sgr: false,
+                bracketed_paste: false,
+                soft_reset: false,
+            }
+        );
+        assert_eq!(
+            VtCaps::for_build(22621),
+            VtCaps {
+                truecolor: true,
+                mouse_sgr: true,
+                bracketed_paste: true,
+                soft_reset: true,
+            }
+        );
+    }
+
+    #[test]
+    fn running_build_is_known() {
+        assert!(build_number().unwrap() > 0);
+    }
+}
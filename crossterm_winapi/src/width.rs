@@ -0,0 +1,53 @@
+//! This module tells how many cells of the console a character takes up.
+//!
+//! East Asian wide and fullwidth characters, like `漢` or `Ａ`, are displayed two cells wide,
+//! and combining marks are drawn on top of the character before them,
+//! so counting characters doesn't give the width of a piece of text.
+
+use unicode_width::UnicodeWidthChar;
+
+/// Get the number of console cells the given character is displayed in.
+///
+/// This is 2 for East Asian wide (W) and fullwidth (F) characters, 0 for characters without a width of their own,
+/// like combining marks and zero width joiners, and 1 otherwise.
+/// The widths are taken from the `unicode-width` crate, which is generated from the East Asian Width property,
+/// `unicode_width::UNICODE_VERSION` tells which version of Unicode it follows.
+///
+/// Characters with an ambiguous width, like `─`, are counted as 1 like the console does outside of East Asian code pages.
+/// Control characters are counted as 1 as well, because they take a single cell when they are written into a cell directly.
+pub fn char_cell_width(c: char) -> u32 {
+    c.width().unwrap_or(1) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::char_cell_width;
+
+    #[test]
+    fn narrow_characters() {
+        assert_eq!(char_cell_width('a'), 1);
+        assert_eq!(char_cell_width('é'), 1);
+        assert_eq!(char_cell_width('─'), 1);
+        assert_eq!(char_cell_width('\t'), 1);
+        assert_eq!(char_cell_width('\u{303F}'), 1);
+        assert_eq!(char_cell_width('\u{FF61}'), 1);
+        // a neutral character between wide emoji.
+        assert_eq!(char_cell_width('\u{1F321}'), 1);
+    }
+
+    #[test]
+    fn wide_characters() {
+        assert_eq!(char_cell_width('漢'), 2);
+        assert_eq!(char_cell_width('한'), 2);
+        assert_eq!(char_cell_width('Ａ'), 2);
+        assert_eq!(char_cell_width('\u{3000}'), 2);
+        assert_eq!(char_cell_width('😀'), 2);
+        assert_eq!(char_cell_width('\u{20000}'), 2);
+    }
+
+    #[test]
+    fn zero_width_characters() {
+        assert_eq!(char_cell_width('\u{0301}'), 0);
+        assert_eq!(char_cell_width('\u{200D}'), 0);
+    }
+}
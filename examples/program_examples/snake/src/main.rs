@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::{stdout, Write};
 use std::iter::Iterator;
 use std::{thread, time};
@@ -27,18 +27,18 @@ fn main() -> Result<()> {
     crossterm.cursor().hide()?;
 
     // initialize free positions for the game map.
-    let mut free_positions: HashMap<String, Position> =
-        HashMap::with_capacity((map_size.width * map_size.height) as usize);
+    let mut free_positions: HashSet<Position> =
+        HashSet::with_capacity((map_size.width * map_size.height) as usize);
 
     // render the map
-    let mut map = Map::new(map_size);
+    let mut map = Map::new(map_size)?;
     map.render_map(&mut free_positions)?;
 
     let mut snake = Snake::new();
 
     // remove snake coords from free positions.
     for part in snake.get_parts().iter() {
-        free_positions.remove_entry(format!("{},{}", part.position.x, part.position.y).as_str());
+        free_positions.remove(&part.position);
     }
 
     map.spawn_food(&free_positions)?;
@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::io::{stdout, Write};
 
-use crossterm::{queue, Colorize, Goto, PrintStyledFont, Result};
+use crossterm::{queue, Color, Colorize, Goto, Output, PrintStyledFont, Result, SetFg};
 use rand;
 use rand::distributions::{IndependentSample, Range};
 
@@ -10,37 +10,79 @@ use super::variables::{Position, Size};
 pub struct Map {
     pub size: Size,
     pub foot_pos: Position,
+    // the styled output of the border, which never changes, so it is only built once.
+    border: Vec<u8>,
 }
 
 impl Map {
-    pub fn new(size: Size) -> Self {
-        Map {
+    pub fn new(size: Size) -> Result<Self> {
+        let mut map = Map {
             size: size,
             foot_pos: Position::new(0, 0),
-        }
+            border: Vec::new(),
+        };
+        map.border = map.build_border()?;
+        Ok(map)
     }
 
     // render the map on the screen.
-    pub fn render_map(&mut self, free_positions: &mut HashMap<String, Position>) -> Result<()> {
-        for y in 0..self.size.height {
-            for x in 0..self.size.height {
-                if (y == 0 || y == self.size.height - 1) || (x == 0 || x == self.size.width - 1) {
-                    queue!(
-                        stdout(),
-                        Goto(x as u16, y as u16),
-                        PrintStyledFont("█".magenta())
-                    )?;
-                } else {
-                    free_positions.insert(format!("{},{}", x, y), Position::new(x, y));
-                }
-            }
+    //
+    // The map is made of a static layer, the border, and a dynamic layer, the food and the snake.
+    // The static layer is written with a single write and flush, only the cells of the dynamic layer are redrawn afterwards.
+    pub fn render_map(&mut self, free_positions: &mut HashSet<Position>) -> Result<()> {
+        let mut stdout = stdout();
+        stdout.write_all(&self.border)?;
+        stdout.flush()?;
+
+        for position in self.cells().filter(|&position| !self.is_border(position)) {
+            free_positions.insert(position);
         }
         Ok(())
     }
 
+    // queue the whole border into a buffer instead of queueing each cell straight to the stdout.
+    //
+    // The color is set with commands, the `Display` of a styled object would write its color reset to the stdout instead of the buffer.
+    // The foreground color is reset at the end, otherwise it would stay magenta for everything drawn after the border.
+    //
+    // Note that on Windows versions without ANSI support commands are executed immediately and nothing is buffered.
+    fn build_border(&self) -> Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+
+        queue!(buffer, SetFg(Color::Magenta))?;
+        for position in self.border_cells() {
+            queue!(
+                buffer,
+                Goto(position.x as u16, position.y as u16),
+                Output("█".to_string())
+            )?;
+        }
+        queue!(buffer, SetFg(Color::Reset))?;
+
+        Ok(buffer)
+    }
+
+    // get the cells on the edges of the map, on which the border is drawn.
+    pub fn border_cells(&self) -> Vec<Position> {
+        self.cells()
+            .filter(|&position| self.is_border(position))
+            .collect()
+    }
+
+    fn is_border(&self, position: Position) -> bool {
+        (position.y == 0 || position.y == self.size.height - 1)
+            || (position.x == 0 || position.x == self.size.width - 1)
+    }
+
+    // iterate over all cells of the map, row by row.
+    fn cells(&self) -> impl Iterator<Item = Position> {
+        let size = self.size;
+        (0..size.height).flat_map(move |y| (0..size.width).map(move |x| Position::new(x, y)))
+    }
+
     pub fn is_out_of_bounds(&self, new_pos: Position) -> bool {
-        if (new_pos.x == 0 || new_pos.x == self.size.width)
-            || (new_pos.y == 0 || new_pos.y == self.size.height)
+        if (new_pos.x == 0 || new_pos.x >= self.size.width - 1)
+            || (new_pos.y == 0 || new_pos.y >= self.size.height - 1)
         {
             return true;
         }
@@ -48,9 +90,9 @@ impl Map {
         return false;
     }
 
-    pub fn spawn_food(&mut self, free_positions: &HashMap<String, Position>) -> Result<()> {
+    pub fn spawn_food(&mut self, free_positions: &HashSet<Position>) -> Result<()> {
         let index = Range::new(0, free_positions.len()).ind_sample(&mut rand::thread_rng());
-        self.foot_pos = free_positions.values().skip(index).next().unwrap().clone();
+        self.foot_pos = *free_positions.iter().nth(index).unwrap();
         self.draw_food()
     }
 
@@ -62,3 +104,47 @@ impl Map {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::Map;
+    use crate::variables::{Position, Size};
+
+    #[test]
+    fn rectangular_border() {
+        let map = Map::new(Size::new(6, 3)).unwrap();
+        let border = map.border_cells();
+
+        // the top and bottom rows and the two side cells of the middle row.
+        assert_eq!(border.len(), 6 + 6 + 2);
+        assert!(border.iter().all(|cell| cell.x < 6 && cell.y < 3));
+        assert!(border
+            .iter()
+            .all(|cell| cell.x == 0 || cell.x == 5 || cell.y == 0 || cell.y == 2));
+    }
+
+    #[test]
+    fn border_resets_the_color() {
+        let map = Map::new(Size::new(6, 3)).unwrap();
+
+        assert!(map.border.starts_with(b"\x1B[38;5;13m"));
+        assert!(map.border.ends_with(b"\x1B[39m"));
+    }
+
+    #[test]
+    fn walls_are_out_of_bounds() {
+        let map = Map::new(Size::new(20, 15)).unwrap();
+
+        // the cells next to each wall are still inside the map.
+        assert!(!map.is_out_of_bounds(Position::new(1, 7)));
+        assert!(!map.is_out_of_bounds(Position::new(18, 7)));
+        assert!(!map.is_out_of_bounds(Position::new(10, 1)));
+        assert!(!map.is_out_of_bounds(Position::new(10, 13)));
+
+        // moving one step further hits the left, right, top and bottom wall.
+        assert!(map.is_out_of_bounds(Position::new(0, 7)));
+        assert!(map.is_out_of_bounds(Position::new(19, 7)));
+        assert!(map.is_out_of_bounds(Position::new(10, 0)));
+        assert!(map.is_out_of_bounds(Position::new(10, 14)));
+    }
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 
 use crossterm::Result;
 
@@ -32,17 +32,14 @@ impl Snake {
     pub fn move_snake(
         &mut self,
         direction: &Direction,
-        free_positions: &mut HashMap<String, Position>,
+        free_positions: &mut HashSet<Position>,
     ) -> Result<()> {
         let count = self.snake_parts.len();
 
         for (index, ref mut snake_part) in self.snake_parts.iter_mut().enumerate() {
             if index == count - 1 {
                 snake_part.position.remove()?;
-                free_positions.insert(
-                    format!("{},{}", snake_part.position.x, snake_part.position.y),
-                    snake_part.position,
-                );
+                free_positions.insert(snake_part.position);
             }
 
             if index == 0 {
@@ -55,9 +52,7 @@ impl Snake {
                     &Direction::Right => snake_part.position.x += 1,
                 }
 
-                free_positions.remove_entry(
-                    format!("{},{}", snake_part.position.x, snake_part.position.y).as_str(),
-                );
+                free_positions.remove(&snake_part.position);
             } else {
                 let new_pos = self.parent_pos.clone();
                 self.parent_pos = snake_part.position.clone();
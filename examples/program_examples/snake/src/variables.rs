@@ -11,7 +11,7 @@ pub enum Direction {
     Right = 3,
 }
 
-#[derive(Copy, Clone, Debug, PartialOrd, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialOrd, PartialEq, Eq, Hash)]
 pub struct Position {
     pub x: usize,
     pub y: usize,
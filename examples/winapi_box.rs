@@ -0,0 +1,66 @@
+//! Draws a colored box with the WinApi console functions directly, without any ANSI escape codes.
+//! This is useful on Windows versions where the console doesn't support virtual terminal processing.
+//!
+//! Run with `cargo run --example winapi_box`.
+
+#[cfg(windows)]
+use std::{thread, time::Duration};
+
+#[cfg(windows)]
+use crossterm_winapi::{attributes, Console, ConsoleColor, Coord, Event, HandleType};
+
+#[cfg(windows)]
+fn draw_box(console: &Console, top_left: Coord, width: u16, height: u16) -> std::io::Result<()> {
+    let box_color = attributes(ConsoleColor::White, ConsoleColor::DarkBlue);
+
+    for row in 0..height as i16 {
+        let start = Coord::new(top_left.x, top_left.y + row);
+
+        // clear the row of the box, and paint its cells in the box color.
+        console.fill_with_character(start, u32::from(width), ' ')?;
+        console.fill_with_attribute(start, u32::from(width), box_color)?;
+    }
+
+    // text written while the guard is alive uses the box color, the previous attributes are restored when it's dropped.
+    let _guard = console.attribute_guard(box_color)?;
+    console.set_cursor_position(Coord::new(top_left.x + 2, top_left.y + height as i16 / 2))?;
+    console.write_str("Press any key to exit")?;
+
+    Ok(())
+}
+
+#[cfg(windows)]
+fn wait_for_key_press() -> std::io::Result<()> {
+    let input = Console::with_handle_type(HandleType::CurrentInputHandle)?;
+
+    loop {
+        // `read_single_input_event` returns `None` when there is no event to read.
+        if let Some(record) = input.read_single_input_event()? {
+            if let Event::Key(key_event) = record.event() {
+                if key_event.key_down {
+                    return Ok(());
+                }
+            }
+        } else {
+            thread::sleep(Duration::from_millis(10));
+        }
+    }
+}
+
+#[cfg(windows)]
+fn main() {
+    let console = Console::new().unwrap();
+
+    draw_box(&console, Coord::new(4, 2), 30, 5).unwrap();
+    wait_for_key_press().unwrap();
+
+    // move the cursor below the box, so the shell prompt doesn't overwrite it.
+    console.set_cursor_position(Coord::new(0, 8)).unwrap();
+}
+
+#[cfg(not(windows))]
+fn main() {
+    println!(
+        "This example uses the WinApi console functions, which are only available on Windows."
+    );
+}